@@ -19,6 +19,7 @@ impl Pillar {
         }
     }
 
+    #[allow(dead_code)]
     pub fn with_style(mut self, style: ShapeStyle) -> Self {
         self.style = style;
         self
@@ -31,6 +32,7 @@ impl Pillar {
         point[1] <= self.y_range[1]
     }
 
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_rectangle(&self) -> Rectangle<(f32, f32)> {
         // Normalize coordinates to [0,1] range
         let x1 = self.x_range[0] / self.bounds[0];
@@ -144,6 +146,7 @@ impl RandomPillar {
         false
     }
 
+    #[allow(dead_code)]
     pub fn precise_contains(&self, point: [f32; 2], radius: f32) -> bool {
         for &[x1, x2, y1, y2] in &self.chambers {
             if point[0] + radius >= x1 && point[0] - radius <= x2 &&
@@ -36,12 +36,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>>  {
         (0..drawing_area[0] as i32, 0..drawing_area[0] as i32),
     ));
     let dot_and_label = |x: f32, y: f32| {
-        return EmptyElement::at((x, y))
-            + Circle::new((0, 0), 3, ShapeStyle::from(&BLACK).filled())
+        EmptyElement::at((x, y))
+            + Circle::new((0, 0), 3, ShapeStyle::from(BLACK).filled())
     };    
     root.fill(&RGBColor(240, 200, 200))?;
 
-    let pillars = pillars;
     for p in pillars {
         root.draw(&p.to_rectangle())?;
     }
@@ -101,9 +100,9 @@ fn q_astar2d(
             }
             neighbors
         },
-        |&[x, y]| {
+        |_| {
             // heuristic set zero like Dijkstra
-            0 as u32
+            0u32
         },
         |&p| p == goal_n, // success condition
     );
@@ -113,9 +112,9 @@ fn q_astar2d(
         Some((path, cost)) => {
             println!("Found 2D path with cost {} ({} steps):", cost, path.len());
             let mut pos_old: [f32; 2] = start;
-            for (_, pos_new) in path.iter().enumerate() {
+            for pos_new in path.iter() {
                 drawable_paths.push((pos_old, *pos_new));
-                pos_old = pos_new.clone();                
+                pos_old = *pos_new;                
             } 
         }
         None => println!("No 2D path found"),
@@ -68,21 +68,21 @@ fn q_astar2d_simple() -> Vec<([f32; 2], [f32; 2])>{
             }
             neighbors
         },
-        |&[x, y]| {
+        |_| {
             // heuristic set zero like Dijkstra
-            0 as u32
+            0u32
         },
         |&p| p == goal_n, // success condition
     );
-    print!("\n");
+    println!();
     let mut drawable_paths: Vec<([f32; 2], [f32; 2])> = vec![];
     match result {
         Some((path, cost)) => {
             println!("Found 2D path with cost {} ({} steps):", cost, path.len());
             let mut pos_old: [f32; 2] = start;
-            for (_, pos_new) in path.iter().enumerate() {
+            for pos_new in path.iter() {
                 drawable_paths.push((pos_old, *pos_new));
-                pos_old = pos_new.clone();                
+                pos_old = *pos_new;                
             } 
         }
         None => println!("No 2D path found"),
@@ -0,0 +1,61 @@
+use quantized_pathfinding::{
+    traits::*,
+    utils::{quantizer::Quantizer, voxel::*},
+    directed::*,
+};
+
+fn main() {
+    let solution = q_astar3d();
+    for (v1, v2) in solution {
+        println!(
+            "({:.2},{:.2},{:.2}) -> ({:.2},{:.2},{:.2})",
+            v1[0], v1[1], v1[2], v2[0], v2[1], v2[2]
+        );
+    }
+}
+
+const N_LEVELS: [usize; 3] = [16, 16, 16];
+
+fn q_astar3d() -> Vec<([f32; 3], [f32; 3])> {
+    let vec_a = [0.0, 0.0, 0.0];
+    let vec_b = [30.0, 30.0, 30.0];
+
+    let start = [1.0, 1.0, 1.0];
+    let goal = [29.0, 29.0, 3.0];
+
+    let quantizer3d = Quantizer::<f32, 3>::with_n(
+        vec_a, vec_b, N_LEVELS
+    );
+
+    // A floor-to-ceiling wall with a window near the top, and a pillar
+    let mut voxels = VoxelMap::from_fn(quantizer3d, |&[x, y, z]| {
+        let wall = (14.0..=16.0).contains(&x) && !(y > 20.0 && z > 20.0);
+        let pillar = (20.0..=24.0).contains(&x) && (20.0..=24.0).contains(&y);
+        wall || pillar
+    });
+    voxels.fill_box([4.0, 4.0, 0.0], [8.0, 8.0, 10.0], true);
+
+    let connectivity = Connectivity::TwentySix;
+    let goal_n = quantizer3d.quantize(goal);
+    let result = quantized_astar(
+        &quantizer3d,
+        start,
+        |n| voxels.successors(n, connectivity),
+        |n| connectivity.distance(*n, goal_n),
+        |&p| p == goal_n, // success condition
+    );
+
+    let mut drawable_paths: Vec<([f32; 3], [f32; 3])> = vec![];
+    match result {
+        Some((path, cost)) => {
+            println!("Found 3D path with cost {} ({} steps):", cost, path.len());
+            let mut pos_old: [f32; 3] = start;
+            for pos_new in path.iter() {
+                drawable_paths.push((pos_old, *pos_new));
+                pos_old = *pos_new;
+            }
+        }
+        None => println!("No 3D path found"),
+    }
+    drawable_paths
+}
@@ -0,0 +1,251 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Debug;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+
+// Float priority for the binary heap, incomparable values count as equal
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FloatKey<T>(T);
+
+impl<T: PartialOrd> Eq for FloatKey<T> {}
+
+impl<T: PartialOrd> PartialOrd for FloatKey<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> Ord for FloatKey<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+type Entry<T, C, const DIM: usize> = Reverse<(FloatKey<T>, C, [usize; DIM])>;
+
+// One round of the anytime search. The cost is at most `bound` times optimal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnytimeSolution<T, C, const DIM: usize> {
+    pub path: Vec<[T; DIM]>,
+    pub cost: C,
+    pub bound: T,
+}
+
+// Anytime Repairing A* (Likhachev, Gordon & Thrun) over quantized cells.
+// Every call to `next` runs one round with an inflated heuristic g + ε·h,
+// reusing the previous rounds, and yields the dequantized path with its
+// suboptimality bound. ε shrinks by `step` per round down to 1, after which
+// the path is optimal and the iterator ends. The bounds need a consistent
+// heuristic, as expanded cells are not reopened within a round. Priorities
+// are compared in float space, `to_float` converts the cost type.
+pub struct AraStar<T, C, Q, FN, FH, FS, FF, const DIM: usize> {
+    quantizer: Q,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+    to_float: FF,
+    epsilon: T,
+    step: T,
+    g: HashMap<[usize; DIM], (C, Option<[usize; DIM]>)>,
+    open: HashSet<[usize; DIM]>,
+    queue: BinaryHeap<Entry<T, C, DIM>>,
+    closed: HashSet<[usize; DIM]>,
+    incons: HashSet<[usize; DIM]>,
+    goal: Option<[usize; DIM]>,
+    done: bool,
+}
+
+impl<T, C, Q, FN, IN, FH, FS, FF, const DIM: usize> AraStar<T, C, Q, FN, FH, FS, FF, DIM>
+where
+    T: FiniteFloat + PartialOrd,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FN: FnMut(&[usize; DIM]) -> IN,
+    IN: IntoIterator<Item = ([usize; DIM], C)>,
+    FH: FnMut(&[usize; DIM]) -> C,
+    FS: FnMut(&[usize; DIM]) -> bool,
+    FF: FnMut(C) -> T,
+{
+    // Starts with ε = 3 lowered by 0.5 per round, see `with_inflation`
+    pub fn new(
+        quantizer: Q,
+        start: [T; DIM],
+        successors: FN,
+        heuristic: FH,
+        mut success: FS,
+        to_float: FF,
+    ) -> Self {
+        let start = quantizer.quantize(start);
+        let goal = success(&start).then_some(start);
+        AraStar {
+            quantizer,
+            successors,
+            heuristic,
+            success,
+            to_float,
+            epsilon: T::from_usize(3),
+            step: T::from_usize(1) / T::from_usize(2),
+            g: HashMap::from([(start, (C::zero(), None))]),
+            open: HashSet::from([start]),
+            queue: BinaryHeap::new(),
+            closed: HashSet::new(),
+            incons: HashSet::new(),
+            goal,
+            done: false,
+        }
+    }
+
+    // Initial inflation ε (at least 1) and its decrease per round.
+    // Panics unless `step` is positive, ε would never reach 1.
+    pub fn with_inflation(mut self, epsilon: T, step: T) -> Self {
+        assert!(step > T::from_usize(0), "inflation step must be positive");
+        let one = T::from_usize(1);
+        self.epsilon = if epsilon < one { one } else { epsilon };
+        self.step = step;
+        self
+    }
+
+    pub fn epsilon(&self) -> T {
+        self.epsilon
+    }
+
+    fn cost(&self, n: &[usize; DIM]) -> Option<C> {
+        self.g.get(n).map(|&(g, _)| g)
+    }
+
+    fn priority(&mut self, n: &[usize; DIM], g: C) -> T {
+        let h = (self.heuristic)(n);
+        (self.to_float)(g) + self.epsilon * (self.to_float)(h)
+    }
+
+    // Rebuild the queue after ε changed
+    fn requeue(&mut self) {
+        self.queue.clear();
+        let open: Vec<_> = self.open.iter().copied().collect();
+        for n in open {
+            let g = self.cost(&n).unwrap();
+            let f = self.priority(&n, g);
+            self.queue.push(Reverse((FloatKey(f), g, n)));
+        }
+    }
+
+    // Smallest valid queue entry
+    fn peek(&mut self) -> Option<(T, [usize; DIM])> {
+        while let Some(&Reverse((FloatKey(f), g, n))) = self.queue.peek() {
+            if self.open.contains(&n) && self.cost(&n) == Some(g) {
+                return Some((f, n));
+            }
+            self.queue.pop();
+        }
+        None
+    }
+
+    fn improve_path(&mut self) {
+        while let Some((f, n)) = self.peek() {
+            if let Some(goal) = self.goal {
+                let goal_g = self.cost(&goal).unwrap();
+                if (self.to_float)(goal_g) <= f {
+                    return;
+                }
+            }
+            self.queue.pop();
+            self.open.remove(&n);
+            self.closed.insert(n);
+            let g = self.cost(&n).unwrap();
+            for (m, step_cost) in (self.successors)(&n) {
+                let next_g = g + step_cost;
+                if self.cost(&m).is_some_and(|old| old <= next_g) {
+                    continue;
+                }
+                self.g.insert(m, (next_g, Some(n)));
+                if (self.success)(&m) && self.goal.is_none_or(|goal| self.cost(&goal).unwrap() > next_g) {
+                    self.goal = Some(m);
+                }
+                if self.closed.contains(&m) {
+                    self.incons.insert(m);
+                } else {
+                    self.open.insert(m);
+                    let f = self.priority(&m, next_g);
+                    self.queue.push(Reverse((FloatKey(f), next_g, m)));
+                }
+            }
+        }
+    }
+
+    // min(ε, g(goal) / min g + h over the open and inconsistent cells)
+    fn bound(&mut self, goal_g: C) -> T {
+        let pending: Vec<_> = self.open.union(&self.incons).copied().collect();
+        let mut lowest: Option<T> = None;
+        for n in pending {
+            let g = self.cost(&n).unwrap();
+            let h = (self.heuristic)(&n);
+            let f = (self.to_float)(g + h);
+            if lowest.is_none_or(|l| f < l) {
+                lowest = Some(f);
+            }
+        }
+        let goal_g = (self.to_float)(goal_g);
+        let one = T::from_usize(1);
+        match lowest {
+            // Nothing left to explore or nothing cheaper left, the path is optimal
+            None => one,
+            Some(l) if goal_g <= l => one,
+            Some(l) if l > T::from_usize(0) && goal_g / l < self.epsilon => goal_g / l,
+            Some(_) => self.epsilon,
+        }
+    }
+
+    fn path(&self, goal: [usize; DIM]) -> Vec<[T; DIM]> {
+        let mut path = vec![];
+        let mut current = Some(goal);
+        while let Some(n) = current {
+            path.push(self.quantizer.dequantize(n));
+            current = self.g[&n].1;
+        }
+        path.reverse();
+        path
+    }
+}
+
+impl<T, C, Q, FN, IN, FH, FS, FF, const DIM: usize> Iterator for AraStar<T, C, Q, FN, FH, FS, FF, DIM>
+where
+    T: FiniteFloat + PartialOrd,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FN: FnMut(&[usize; DIM]) -> IN,
+    IN: IntoIterator<Item = ([usize; DIM], C)>,
+    FH: FnMut(&[usize; DIM]) -> C,
+    FS: FnMut(&[usize; DIM]) -> bool,
+    FF: FnMut(C) -> T,
+{
+    type Item = AnytimeSolution<T, C, DIM>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.requeue();
+        self.improve_path();
+        let Some(goal) = self.goal else {
+            self.done = true;
+            return None;
+        };
+        let cost = self.cost(&goal).unwrap();
+        let bound = self.bound(cost);
+        let solution = AnytimeSolution { path: self.path(goal), cost, bound };
+
+        // Prepare the next round
+        let one = T::from_usize(1);
+        if bound <= one {
+            self.done = true;
+        } else {
+            let lowered = self.epsilon - self.step;
+            self.epsilon = if lowered < one { one } else { lowered };
+            self.open.extend(self.incons.drain());
+            self.closed.clear();
+        }
+        Some(solution)
+    }
+}
@@ -0,0 +1,141 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::Debug;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use super::space_time_astar::{space_time_search, CellTimes, TimedPath};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Constraint<const DIM: usize> {
+    // The agent may not be in `cell` at time step `t`
+    Vertex { agent: usize, cell: [usize; DIM], t: usize },
+    // The agent may not move from `from` to `to` arriving at `t`
+    Edge { agent: usize, from: [usize; DIM], to: [usize; DIM], t: usize },
+}
+
+struct Node<C, const DIM: usize> {
+    constraints: Vec<Constraint<DIM>>,
+    paths: Vec<CellTimes<DIM>>,
+    costs: Vec<C>,
+}
+
+// Cell of an agent at time step `t`, it stays at its goal after arriving
+fn position<const DIM: usize>(path: &CellTimes<DIM>, t: usize) -> [usize; DIM] {
+    path.get(t).unwrap_or(path.last().unwrap()).0
+}
+
+// First vertex or edge conflict, as the constraint for each agent involved
+fn first_conflict<const DIM: usize>(paths: &[CellTimes<DIM>]) -> Option<[Constraint<DIM>; 2]> {
+    let makespan = paths.iter().map(|p| p.len()).max().unwrap_or(0);
+    for t in 0..makespan {
+        for i in 0..paths.len() {
+            for j in i + 1..paths.len() {
+                let (ni, nj) = (position(&paths[i], t), position(&paths[j], t));
+                if ni == nj {
+                    return Some([
+                        Constraint::Vertex { agent: i, cell: ni, t },
+                        Constraint::Vertex { agent: j, cell: nj, t },
+                    ]);
+                }
+                if t == 0 {
+                    continue;
+                }
+                let (pi, pj) = (position(&paths[i], t - 1), position(&paths[j], t - 1));
+                if pi == nj && pj == ni && pi != ni {
+                    return Some([
+                        Constraint::Edge { agent: i, from: pi, to: ni, t },
+                        Constraint::Edge { agent: j, from: pj, to: nj, t },
+                    ]);
+                }
+            }
+        }
+    }
+    None
+}
+
+// Conflict-Based Search (Sharon et al.) for agents sharing a quantized grid.
+// Each agent is planned alone with space-time A*, and whenever two paths
+// meet in a cell or swap cells the search branches on which of the two has
+// to avoid it. The sum of path costs is optimal within `horizon` steps.
+// `heuristic(n, goal)` estimates the cost from `n` to an agent's goal.
+// Returns one timed path per agent, in the order of `agents`.
+pub fn quantized_cbs<T, C, const DIM: usize, Q, FN, IN, FH>(
+    quantizer: &Q,
+    agents: &[([T; DIM], [T; DIM])],
+    horizon: usize,
+    wait_cost: C,
+    mut successors: FN,
+    mut heuristic: FH,
+) -> Option<(Vec<TimedPath<T, DIM>>, C)>
+where
+    T: FiniteFloat,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FN: FnMut(&[usize; DIM]) -> IN,
+    IN: IntoIterator<Item = ([usize; DIM], C)>,
+    FH: FnMut(&[usize; DIM], &[usize; DIM]) -> C,
+{
+    let cells: Vec<_> = agents.iter()
+        .map(|&(start, goal)| (quantizer.quantize(start), quantizer.quantize(goal)))
+        .collect();
+    let mut low_level = |agent: usize, constraints: &[Constraint<DIM>]| {
+        let (start, goal) = cells[agent];
+        space_time_search(
+            start,
+            0,
+            horizon,
+            wait_cost,
+            &mut successors,
+            |n| heuristic(n, &goal),
+            |n| *n == goal,
+            |from, to, t| constraints.iter().any(|&c| match c {
+                Constraint::Vertex { agent: a, cell, t: ct } => a == agent && cell == *to && ct == t,
+                Constraint::Edge { agent: a, from: cf, to: ct_to, t: ct } => {
+                    a == agent && cf == *from && ct_to == *to && ct == t
+                }
+            }),
+        )
+    };
+
+    let mut root = Node { constraints: vec![], paths: vec![], costs: vec![] };
+    for agent in 0..agents.len() {
+        let (path, cost) = low_level(agent, &[])?;
+        root.paths.push(path);
+        root.costs.push(cost);
+    }
+    let total = |node: &Node<C, DIM>| node.costs.iter().fold(C::zero(), |sum, &c| sum + c);
+
+    let mut nodes = vec![];
+    let mut open = BinaryHeap::new();
+    open.push(Reverse((total(&root), 0)));
+    nodes.push(root);
+    while let Some(Reverse((cost, index))) = open.pop() {
+        let Some(conflict) = first_conflict(&nodes[index].paths) else {
+            let paths = nodes[index].paths.iter()
+                .map(|path| path.iter().map(|&(n, t)| (t, quantizer.dequantize(n))).collect())
+                .collect();
+            return Some((paths, cost));
+        };
+        for constraint in conflict {
+            let agent = match constraint {
+                Constraint::Vertex { agent, .. } | Constraint::Edge { agent, .. } => agent,
+            };
+            let mut constraints = nodes[index].constraints.clone();
+            constraints.push(constraint);
+            let Some((path, agent_cost)) = low_level(agent, &constraints) else {
+                continue;
+            };
+            let mut child = Node {
+                constraints,
+                paths: nodes[index].paths.clone(),
+                costs: nodes[index].costs.clone(),
+            };
+            child.paths[agent] = path;
+            child.costs[agent] = agent_cost;
+            open.push(Reverse((total(&child), nodes.len())));
+            nodes.push(child);
+        }
+    }
+    None
+}
@@ -0,0 +1,77 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Debug;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+
+struct Node<T, C, const DIM: usize> {
+    state: [T; DIM],
+    parent: Option<usize>,
+    cost: C,
+}
+
+// A* whose successors work on float states. The quantizer is only used to
+// tell which states share a cell: each cell keeps its cheapest state and is
+// expanded once. Unlike `quantized_astar`, the path holds the exact states
+// that were visited rather than dequantized lattice points.
+pub fn quantized_continuous_astar<T, C, const DIM: usize, Q, FN, IN, FH, FS>(
+    quantizer: &Q,
+    start: [T; DIM],
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<[T; DIM]>, C)>
+where
+    T: FiniteFloat,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FN: FnMut(&[T; DIM]) -> IN,
+    IN: IntoIterator<Item = ([T; DIM], C)>,
+    FH: FnMut(&[T; DIM]) -> C,
+    FS: FnMut(&[T; DIM]) -> bool,
+{
+    let mut nodes = vec![Node { state: start, parent: None, cost: C::zero() }];
+    let mut best: HashMap<[usize; DIM], usize> = HashMap::new();
+    let mut closed: HashSet<[usize; DIM]> = HashSet::new();
+    let mut open = BinaryHeap::new();
+    best.insert(quantizer.quantize(start), 0);
+    open.push(Reverse((heuristic(&start), 0)));
+
+    while let Some(Reverse((_, i))) = open.pop() {
+        let cell = quantizer.quantize(nodes[i].state);
+        if best.get(&cell) != Some(&i) || !closed.insert(cell) {
+            continue;
+        }
+        if success(&nodes[i].state) {
+            let cost = nodes[i].cost;
+            let mut path = vec![];
+            let mut current = Some(i);
+            while let Some(j) = current {
+                path.push(nodes[j].state);
+                current = nodes[j].parent;
+            }
+            path.reverse();
+            return Some((path, cost));
+        }
+        let state = nodes[i].state;
+        let cost = nodes[i].cost;
+        for (next, step_cost) in successors(&state) {
+            let next_cell = quantizer.quantize(next);
+            if closed.contains(&next_cell) {
+                continue;
+            }
+            let next_cost = cost + step_cost;
+            if let Some(&j) = best.get(&next_cell)
+                && nodes[j].cost <= next_cost
+            {
+                continue;
+            }
+            let j = nodes.len();
+            nodes.push(Node { state: next, parent: Some(i), cost: next_cost });
+            best.insert(next_cell, j);
+            open.push(Reverse((next_cost + heuristic(&next), j)));
+        }
+    }
+    None
+}
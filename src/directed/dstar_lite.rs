@@ -0,0 +1,209 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::grid::grid_neighbors;
+use super::incremental::{region_cells, CostGrid, IncrementalSearch, Key};
+
+// D* Lite (Koenig & Likhachev) over the cells of a quantizer. The search runs
+// backwards from the goal, so when the agent moves or cell costs change only
+// the affected part of the previous search is repaired. Stepping into a cell
+// costs that cell's cost, None marks it blocked. `heuristic(a, b)` estimates
+// the cost between two cells and must be consistent, `|_, _| C::zero()` is.
+pub struct DStarLite<T, C, Q, FH, const DIM: usize> {
+    quantizer: Q,
+    offsets: Vec<[isize; DIM]>,
+    heuristic: FH,
+    costs: CostGrid<C, DIM>,
+    search: IncrementalSearch<C, DIM>,
+    start: [usize; DIM],
+    goal: [usize; DIM],
+    last: [usize; DIM],     // Start when the costs last changed
+    km: C,
+    _float: PhantomData<T>,
+}
+
+impl<T, C, Q, FH, const DIM: usize> DStarLite<T, C, Q, FH, DIM>
+where
+    T: FiniteFloat,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FH: FnMut(&[usize; DIM], &[usize; DIM]) -> C,
+{
+    pub fn new<FC>(
+        quantizer: Q,
+        start: [T; DIM],
+        goal: [T; DIM],
+        offsets: &[[isize; DIM]],
+        cost: FC,
+        heuristic: FH,
+    ) -> Self
+    where
+        FC: FnMut(&[usize; DIM]) -> Option<C>,
+    {
+        let n_levels = quantizer.n_levels();
+        let costs = CostGrid::new(n_levels, cost);
+        let start = quantizer.quantize(start);
+        let goal = quantizer.quantize(goal);
+        let mut planner = DStarLite {
+            quantizer,
+            offsets: offsets.to_vec(),
+            heuristic,
+            costs,
+            search: IncrementalSearch::new(n_levels),
+            start,
+            goal,
+            last: start,
+            km: C::zero(),
+            _float: PhantomData,
+        };
+        if planner.costs.index(goal).is_some() {
+            planner.search.set_rhs(goal, Some(C::zero()));
+            let key = planner.key(goal);
+            planner.search.push(goal, key);
+        }
+        planner
+    }
+
+    pub fn quantizer(&self) -> &Q {
+        &self.quantizer
+    }
+
+    pub fn start(&self) -> [usize; DIM] {
+        self.start
+    }
+
+    pub fn goal(&self) -> [usize; DIM] {
+        self.goal
+    }
+
+    pub fn cost(&self, n: [usize; DIM]) -> Option<C> {
+        self.costs.cost(n)
+    }
+
+    // Cost-to-goal of the last repaired search, None if unreachable
+    pub fn cost_to_goal(&self, n: [usize; DIM]) -> Option<C> {
+        self.search.g(n)
+    }
+
+    // The agent moved, the next plan starts from `x`
+    pub fn move_start(&mut self, x: [T; DIM]) {
+        self.start = self.quantizer.quantize(x);
+    }
+
+    // Change the cost of a single cell
+    pub fn update_cell(&mut self, n: [usize; DIM], cost: Option<C>) {
+        self.update_cells([n], cost);
+    }
+
+    // Change the cost of several cells at once
+    pub fn update_cells<I>(&mut self, cells: I, cost: Option<C>)
+    where
+        I: IntoIterator<Item = [usize; DIM]>,
+    {
+        let changed = self.costs.update_cells(cells, cost);
+        if changed.is_empty() {
+            return;
+        }
+        self.km = self.km + (self.heuristic)(&self.last, &self.start);
+        self.last = self.start;
+        // Edges into and out of a changed cell change
+        for n in changed {
+            self.update_vertex(n);
+            for m in grid_neighbors(&self.quantizer, n, &self.offsets) {
+                self.update_vertex(m);
+            }
+        }
+    }
+
+    // Change the cost of every cell between the world-space corners `min` and `max`
+    pub fn update_region(&mut self, min: [T; DIM], max: [T; DIM], cost: Option<C>) {
+        self.update_cells(region_cells(&self.quantizer, min, max), cost);
+    }
+
+    // Repair the search until the start is consistent
+    pub fn compute_shortest_path(&mut self) {
+        while let Some(top) = self.search.top_key() {
+            if self.costs.index(self.start).is_none() {
+                return;
+            }
+            // An unreachable start is only settled once the queue runs dry
+            if self.search.is_settled(self.start) && top >= self.key(self.start) {
+                return;
+            }
+            let Some((k_old, u)) = self.search.pop() else {
+                return;
+            };
+            let k_new = self.key(u);
+            if k_old < k_new {
+                self.search.push(u, k_new);
+            } else if self.search.is_overconsistent(u) {
+                self.search.set_g(u, self.search.rhs(u));
+                for p in grid_neighbors(&self.quantizer, u, &self.offsets) {
+                    self.update_vertex(p);
+                }
+            } else {
+                self.search.set_g(u, None);
+                self.update_vertex(u);
+                for p in grid_neighbors(&self.quantizer, u, &self.offsets) {
+                    self.update_vertex(p);
+                }
+            }
+        }
+    }
+
+    // Repair and follow the cheapest successors to the goal, dequantized
+    pub fn plan(&mut self) -> Option<(Vec<[T; DIM]>, C)> {
+        self.compute_shortest_path();
+        self.path()
+    }
+
+    // Path of the last repaired search, None if the goal is unreachable
+    // or the start or the goal is blocked
+    pub fn path(&self) -> Option<(Vec<[T; DIM]>, C)> {
+        self.cost(self.start)?;
+        self.cost(self.goal)?;
+        self.cost_to_goal(self.start)?;
+        let mut path = vec![self.quantizer.dequantize(self.start)];
+        let mut total = C::zero();
+        let mut n = self.start;
+        while n != self.goal {
+            if path.len() > self.costs.len() {
+                return None;
+            }
+            let (m, step) = grid_neighbors(&self.quantizer, n, &self.offsets).into_iter()
+                .filter_map(|m| {
+                    let step = self.costs.edge_cost(n, m)?;
+                    let rest = self.cost_to_goal(m)?;
+                    Some((m, step, step + rest))
+                })
+                .min_by_key(|&(_, _, through)| through)
+                .map(|(m, step, _)| (m, step))?;
+            total = total + step;
+            path.push(self.quantizer.dequantize(m));
+            n = m;
+        }
+        Some((path, total))
+    }
+
+    fn key(&mut self, n: [usize; DIM]) -> Key<C> {
+        let h = (self.heuristic)(&self.start, &n) + self.km;
+        self.search.key(n, h)
+    }
+
+    fn update_vertex(&mut self, u: [usize; DIM]) {
+        if self.costs.index(u).is_none() {
+            return;
+        }
+        if u != self.goal {
+            let rhs = grid_neighbors(&self.quantizer, u, &self.offsets).into_iter()
+                .filter_map(|m| Some(self.costs.edge_cost(u, m)? + self.cost_to_goal(m)?))
+                .min();
+            self.search.set_rhs(u, rhs);
+        }
+        let (start, km) = (self.start, self.km);
+        let heuristic = &mut self.heuristic;
+        self.search.requeue(u, || heuristic(&start, &u) + km);
+    }
+}
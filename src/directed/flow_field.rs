@@ -0,0 +1,127 @@
+use std::fmt::Debug;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::grid::{face_offsets, flat_index, grid_neighbors, n_cells, unflat_index};
+use crate::utils::interpolation::wrapping_interpolate_vector;
+use super::{quantized_dijkstra_field, DistanceField};
+
+// Per-cell steering towards a shared goal.
+// Every reachable cell points to the next cell on its cheapest path,
+// the goal itself points nowhere (zero vector).
+#[derive(Debug, Clone)]
+pub struct FlowField<T: FiniteFloat, const DIM: usize> {
+    pub n_levels: [usize; DIM],
+    next: Vec<Option<[usize; DIM]>>,
+    directions: Vec<Option<[T; DIM]>>,
+}
+
+impl<T: FiniteFloat, const DIM: usize> FlowField<T, DIM> {
+    // Follow the Dijkstra parents of an integration field, sources point to themselves
+    pub fn from_distance_field<C, Q>(quantizer: &Q, field: &DistanceField<C, DIM>) -> Self
+    where
+        C: Copy,
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        let n_levels = field.n_levels;
+        let mut next = vec![None; n_cells(n_levels)];
+        let mut directions = vec![None; n_cells(n_levels)];
+
+        for (n, _) in field.iter() {
+            let m = field.parent(n).unwrap_or(n);
+            let from = quantizer.dequantize(n);
+            let to = quantizer.dequantize(m);
+            let i = flat_index(n, n_levels).unwrap();
+            next[i] = Some(m);
+            directions[i] = Some(quantizer.wrapped_difference(from, to));
+        }
+        FlowField { n_levels, next, directions }
+    }
+
+    // World-space offset to the next cell, None if the goal is unreachable
+    pub fn direction(&self, n: [usize; DIM]) -> Option<[T; DIM]> {
+        flat_index(n, self.n_levels).and_then(|i| self.directions[i])
+    }
+
+    pub fn next_cell(&self, n: [usize; DIM]) -> Option<[usize; DIM]> {
+        flat_index(n, self.n_levels).and_then(|i| self.next[i])
+    }
+
+    // Direction at the cell the float position falls into
+    pub fn sample<Q>(&self, quantizer: &Q, x: [T; DIM]) -> Option<[T; DIM]>
+    where
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        self.direction(quantizer.quantize(x))
+    }
+
+    // Direction blended from the surrounding cells, for smooth steering
+    pub fn interpolate<Q>(&self, quantizer: &Q, x: [T; DIM]) -> Option<[T; DIM]>
+    where
+        T: PartialOrd,
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        wrapping_interpolate_vector(
+            quantizer.cell_coordinate(x),
+            self.n_levels,
+            quantizer.periodic(),
+            |n| self.direction(n),
+        )
+    }
+
+    // Follow the field from a float position down to the goal
+    pub fn path_from<Q>(&self, quantizer: &Q, start: [T; DIM]) -> Option<Vec<[T; DIM]>>
+    where
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        let mut n = quantizer.quantize(start);
+        let mut path = vec![quantizer.dequantize(n)];
+        loop {
+            let m = self.next_cell(n)?;
+            if m == n {
+                return Some(path);
+            }
+            path.push(quantizer.dequantize(m));
+            n = m;
+        }
+    }
+
+    // Reachable cells with their directions
+    pub fn iter(&self) -> impl Iterator<Item = ([usize; DIM], [T; DIM])> + '_ {
+        self.directions.iter()
+            .enumerate()
+            .filter_map(|(i, d)| d.map(|d| (unflat_index(i, self.n_levels), d)))
+    }
+}
+
+// Flow field over face-connected cells.
+// `cost` is the cost of entering a cell, None for blocked cells.
+pub fn quantized_flow_field<T, C, const DIM: usize, Q, FC>(
+    quantizer: &Q,
+    goal: [T; DIM],
+    mut cost: FC,
+) -> FlowField<T, DIM>
+where
+    T: FiniteFloat,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FC: FnMut(&[usize; DIM]) -> Option<C>,
+{
+    let n_levels = quantizer.n_levels();
+    let offsets = face_offsets::<DIM>();
+
+    // Reversed edges weighted by the cell being left, so that the integration
+    // value of a cell includes its own cost and following its parents is optimal.
+    // A blocked goal leaves the field empty.
+    let goal_n = quantizer.quantize(goal);
+    let source = flat_index(goal_n, n_levels)
+        .and_then(|_| cost(&goal_n))
+        .map(|_| goal);
+    let field = quantized_dijkstra_field(quantizer, source, |&n| {
+        grid_neighbors(quantizer, n, &offsets)
+            .into_iter()
+            .filter_map(|m| cost(&m).map(|c| (m, c)))
+            .collect::<Vec<_>>()
+    });
+    FlowField::from_distance_field(quantizer, &field)
+}
@@ -0,0 +1,285 @@
+use std::array::from_fn;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use pathfinding::directed::dijkstra::{dijkstra, dijkstra_all};
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::grid::{face_offsets, flat_index, n_cells, offset_cell, unflat_index};
+use crate::utils::quantizer::{Alignment, Quantizer};
+use super::quantized_astar;
+
+// HPA*-style planner. The fine grid is cut into clusters of `cluster_size`
+// cells, which are the levels of a coarse quantizer over the same bounds.
+// Portals are placed where neighboring clusters touch through passable cells
+// (one per contiguous opening) and linked inside each cluster once, up front.
+// A query searches this small portal graph and then refines each leg with
+// `quantized_astar` at fine resolution. Paths are near-optimal, not optimal.
+#[derive(Debug, Clone)]
+pub struct HierarchicalPlanner<T: FiniteFloat, C, const DIM: usize> {
+    fine: Quantizer<T, DIM>,
+    coarse: Quantizer<T, DIM>,
+    cluster_size: [usize; DIM],
+    costs: Vec<Option<C>>,        // Cost of entering each fine cell, None if blocked
+    portals: Vec<[usize; DIM]>,
+    edges: Vec<Vec<(usize, C)>>,
+}
+
+impl<T, C, const DIM: usize> HierarchicalPlanner<T, C, DIM>
+where
+    T: FiniteFloat,
+    C: Zero + Ord + Copy + Debug,
+{
+    // `cost` is the cost of entering a fine cell, None for blocked cells
+    pub fn new<FC>(fine: Quantizer<T, DIM>, cluster_size: [usize; DIM], mut cost: FC) -> Self
+    where
+        FC: FnMut(&[usize; DIM]) -> Option<C>,
+    {
+        let n_levels = fine.n_levels;
+        let cluster_size = cluster_size.map(|s| s.max(1));
+        let n_clusters = from_fn(|i| n_levels[i].div_ceil(cluster_size[i]));
+
+        // Clusters are cell-centered levels covering whole fine cells
+        let step = fine.step_size();
+        let a = from_fn(|i| fine.lower_bounds()[i] - step[i] / T::from_usize(2));
+        let b = from_fn(|i| a[i] + T::from_usize(n_clusters[i] * cluster_size[i]) * step[i]);
+        let coarse = Quantizer::with_n_aligned(a, b, n_clusters, Alignment::CellCentered);
+
+        let costs = (0..n_cells(n_levels))
+            .map(|i| cost(&unflat_index(i, n_levels)))
+            .collect();
+        let mut planner = HierarchicalPlanner {
+            fine,
+            coarse,
+            cluster_size,
+            costs,
+            portals: vec![],
+            edges: vec![],
+        };
+        planner.build_portals();
+        planner.link_clusters();
+        planner
+    }
+
+    pub fn fine_quantizer(&self) -> &Quantizer<T, DIM> {
+        &self.fine
+    }
+
+    pub fn coarse_quantizer(&self) -> &Quantizer<T, DIM> {
+        &self.coarse
+    }
+
+    // Portal cells in world space
+    pub fn portals(&self) -> impl Iterator<Item = [T; DIM]> + '_ {
+        self.portals.iter().map(|&n| self.fine.dequantize(n))
+    }
+
+    pub fn cluster_of(&self, n: [usize; DIM]) -> [usize; DIM] {
+        from_fn(|i| n[i] / self.cluster_size[i])
+    }
+
+    fn cost(&self, n: [usize; DIM]) -> Option<C> {
+        flat_index(n, self.fine.n_levels).and_then(|i| self.costs[i])
+    }
+
+    fn add_portal(&mut self, index: &mut HashMap<[usize; DIM], usize>, n: [usize; DIM]) -> usize {
+        *index.entry(n).or_insert_with(|| {
+            self.portals.push(n);
+            self.edges.push(vec![]);
+            self.portals.len() - 1
+        })
+    }
+
+    // One portal pair per contiguous opening of every border between clusters
+    fn build_portals(&mut self) {
+        let n_levels = self.fine.n_levels;
+        let mut openings: HashMap<([usize; DIM], usize), Vec<[usize; DIM]>> = HashMap::new();
+        for i in 0..n_cells(n_levels) {
+            let n = unflat_index(i, n_levels);
+            if self.cost(n).is_none() {
+                continue;
+            }
+            for axis in 0..DIM {
+                if (n[axis] + 1) % self.cluster_size[axis] != 0 {
+                    continue;
+                }
+                let mut m = n;
+                m[axis] += 1;
+                if self.cost(m).is_some() {
+                    openings.entry((self.cluster_of(n), axis)).or_default().push(n);
+                }
+            }
+        }
+
+        let mut index = HashMap::new();
+        let mut keys: Vec<_> = openings.keys().copied().collect();
+        keys.sort();
+        for key in keys {
+            let (_, axis) = key;
+            for opening in contiguous(&openings[&key], axis) {
+                let n = opening[opening.len() / 2];
+                let mut m = n;
+                m[axis] += 1;
+                let (cost_n, cost_m) = (self.cost(n).unwrap(), self.cost(m).unwrap());
+                let p = self.add_portal(&mut index, n);
+                let q = self.add_portal(&mut index, m);
+                self.edges[p].push((q, cost_m));
+                self.edges[q].push((p, cost_n));
+            }
+        }
+    }
+
+    // Portal to portal costs inside each cluster
+    fn link_clusters(&mut self) {
+        let mut by_cluster: HashMap<[usize; DIM], Vec<usize>> = HashMap::new();
+        for (p, &n) in self.portals.iter().enumerate() {
+            by_cluster.entry(self.cluster_of(n)).or_default().push(p);
+        }
+        for members in by_cluster.values() {
+            for &p in members {
+                let reached = self.costs_from(self.portals[p]);
+                for &q in members {
+                    if p != q
+                        && let Some(&c) = reached.get(&self.portals[q])
+                    {
+                        self.edges[p].push((q, c));
+                    }
+                }
+            }
+        }
+    }
+
+    // Passable face neighbors staying in the cluster of `n`
+    fn cluster_neighbors(&self, n: [usize; DIM]) -> Vec<[usize; DIM]> {
+        let cluster = self.cluster_of(n);
+        face_offsets::<DIM>().into_iter()
+            .filter_map(|offset| offset_cell(n, offset, self.fine.n_levels))
+            .filter(|&m| self.cluster_of(m) == cluster && self.cost(m).is_some())
+            .collect()
+    }
+
+    // Costs from `n` to the cells of its cluster
+    fn costs_from(&self, n: [usize; DIM]) -> HashMap<[usize; DIM], C> {
+        let mut reached: HashMap<_, _> = dijkstra_all(&n, |&m| {
+            self.cluster_neighbors(m).into_iter()
+                .map(|k| (k, self.cost(k).unwrap()))
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .map(|(m, (_, c))| (m, c))
+        .collect();
+        reached.insert(n, C::zero());
+        reached
+    }
+
+    // Costs from the cells of its cluster to `n`, over reversed edges
+    fn costs_to(&self, n: [usize; DIM]) -> HashMap<[usize; DIM], C> {
+        let mut reached: HashMap<_, _> = dijkstra_all(&n, |&m| {
+            let enter = self.cost(m).unwrap();
+            self.cluster_neighbors(m).into_iter()
+                .map(|k| (k, enter))
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .map(|(m, (_, c))| (m, c))
+        .collect();
+        reached.insert(n, C::zero());
+        reached
+    }
+
+    pub fn plan(&self, start: [T; DIM], goal: [T; DIM]) -> Option<(Vec<[T; DIM]>, C)> {
+        let s = self.fine.quantize(start);
+        let g = self.fine.quantize(goal);
+        self.cost(s)?;
+        self.cost(g)?;
+
+        // Start and goal join the portal graph as two extra nodes
+        let s_node = self.portals.len();
+        let g_node = s_node + 1;
+        let from_start = self.costs_from(s);
+        let to_goal = self.costs_to(g);
+        let same_cluster = self.cluster_of(s) == self.cluster_of(g);
+        let cell = |node: usize| {
+            if node == s_node {
+                s
+            } else if node == g_node {
+                g
+            } else {
+                self.portals[node]
+            }
+        };
+
+        let (route, cost) = dijkstra(
+            &s_node,
+            |&node| {
+                let mut next: Vec<(usize, C)> = vec![];
+                if node == s_node {
+                    next.extend(self.portals.iter()
+                        .enumerate()
+                        .filter_map(|(p, n)| from_start.get(n).map(|&c| (p, c))));
+                    if same_cluster && let Some(&c) = from_start.get(&g) {
+                        next.push((g_node, c));
+                    }
+                } else if node < s_node {
+                    next.extend(self.edges[node].iter().copied());
+                    if let Some(&c) = to_goal.get(&self.portals[node]) {
+                        next.push((g_node, c));
+                    }
+                }
+                next
+            },
+            |&node| node == g_node,
+        )?;
+
+        let mut path = vec![self.fine.dequantize(s)];
+        for leg in route.windows(2) {
+            let (from, to) = (cell(leg[0]), cell(leg[1]));
+            if from == to {
+                continue;
+            }
+            if self.cluster_of(from) != self.cluster_of(to) {
+                path.push(self.fine.dequantize(to));
+                continue;
+            }
+            let (refined, _) = quantized_astar(
+                &self.fine,
+                self.fine.dequantize(from),
+                |&n| {
+                    self.cluster_neighbors(n).into_iter()
+                        .map(|m| (m, self.cost(m).unwrap()))
+                        .collect::<Vec<_>>()
+                },
+                |_| C::zero(),
+                |&n| n == to,
+            )?;
+            path.extend(refined.into_iter().skip(1));
+        }
+        Some((path, cost))
+    }
+}
+
+// Split the border cells of one face into contiguous openings
+fn contiguous<const DIM: usize>(cells: &[[usize; DIM]], axis: usize) -> Vec<Vec<[usize; DIM]>> {
+    let mut remaining: Vec<[usize; DIM]> = cells.to_vec();
+    remaining.sort();
+    let mut openings = vec![];
+    while let Some(first) = remaining.first().copied() {
+        remaining.retain(|&n| n != first);
+        let mut opening = vec![first];
+        let mut queue = VecDeque::from([first]);
+        while let Some(n) = queue.pop_front() {
+            let touching: Vec<[usize; DIM]> = remaining.iter()
+                .copied()
+                .filter(|m| {
+                    (0..DIM).filter(|&i| i != axis).map(|i| n[i].abs_diff(m[i])).sum::<usize>() == 1
+                })
+                .collect();
+            remaining.retain(|m| !touching.contains(m));
+            opening.extend(touching.iter().copied());
+            queue.extend(touching);
+        }
+        opening.sort();
+        openings.push(opening);
+    }
+    openings
+}
@@ -0,0 +1,80 @@
+use std::fmt::Debug;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, TranscendentalFloat};
+use crate::utils::float::wrap_angle;
+use super::quantized_continuous_astar;
+
+// One steering arc of a car-like agent.
+// `curvature` is 1 / turning radius (0 for straight, signed for left/right),
+// `length` is the driven arc length (negative for reversing).
+#[derive(Debug, Clone, Copy)]
+pub struct SteeringPrimitive<T, C> {
+    pub curvature: T,
+    pub length: T,
+    pub cost: C,
+}
+
+// Pose [x, y, heading] after driving an arc of the given curvature and length
+pub fn simulate_arc<T: TranscendentalFloat>([x, y, theta]: [T; 3], curvature: T, length: T) -> [T; 3] {
+    let zero = T::from_usize(0);
+    if curvature == zero {
+        return [x + length * theta.cos(), y + length * theta.sin(), theta];
+    }
+    let end = theta + curvature * length;
+    [
+        x + (end.sin() - theta.sin()) / curvature,
+        y + (theta.cos() - end.cos()) / curvature,
+        wrap_angle(end),
+    ]
+}
+
+// Poses along the arc, `steps` evenly spaced ones ending at the arc's end
+pub fn trace_arc<T: TranscendentalFloat>(pose: [T; 3], curvature: T, length: T, steps: usize) -> Vec<[T; 3]> {
+    let steps = steps.max(1);
+    (1..=steps)
+        .map(|k| simulate_arc(pose, curvature, length * T::from_usize(k) / T::from_usize(steps)))
+        .collect()
+}
+
+// Hybrid A* for car-like agents over [x, y, heading].
+// Poses are kept exactly while the quantizer only decides which poses count as
+// the same search node, so each cell is expanded at most once. Successors come
+// from driving every primitive; `valid` is checked at `steps` poses along each
+// arc. The returned path holds the exact pose after each primitive, starting
+// with `start`, so consecutive poses are joined by one drivable arc.
+#[allow(clippy::too_many_arguments)]
+pub fn quantized_hybrid_astar<T, C, Q, FV, FH, FS>(
+    quantizer: &Q,
+    start: [T; 3],
+    primitives: &[SteeringPrimitive<T, C>],
+    steps: usize,
+    mut valid: FV,
+    heuristic: FH,
+    success: FS,
+) -> Option<(Vec<[T; 3]>, C)>
+where
+    T: TranscendentalFloat,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, 3>,
+    FV: FnMut(&[T; 3]) -> bool,
+    FH: FnMut(&[T; 3]) -> C,
+    FS: FnMut(&[T; 3]) -> bool,
+{
+    quantized_continuous_astar(
+        quantizer,
+        start,
+        |pose| {
+            primitives.iter()
+                .filter(|p| {
+                    trace_arc(*pose, p.curvature, p.length, steps)
+                        .iter()
+                        .all(&mut valid)
+                })
+                .map(|p| (simulate_arc(*pose, p.curvature, p.length), p.cost))
+                .collect::<Vec<_>>()
+        },
+        heuristic,
+        success,
+    )
+}
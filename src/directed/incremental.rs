@@ -0,0 +1,185 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use pathfinding::num_traits::Zero;
+
+use crate::traits::HasQuantizationMethods;
+use crate::utils::grid::{flat_index, n_cells, unflat_index, wrapping_box_cells};
+
+pub(super) type Key<C> = (C, C);
+
+// Cell costs of `LpaStar` and `DStarLite`. Stepping into a cell costs that
+// cell's cost, None marks it blocked.
+#[derive(Debug, Clone)]
+pub(super) struct CostGrid<C, const DIM: usize> {
+    n_levels: [usize; DIM],
+    costs: Vec<Option<C>>,
+}
+
+impl<C: Copy + PartialEq, const DIM: usize> CostGrid<C, DIM> {
+    pub(super) fn new<FC>(n_levels: [usize; DIM], mut cost: FC) -> Self
+    where
+        FC: FnMut(&[usize; DIM]) -> Option<C>,
+    {
+        let costs = (0..n_cells(n_levels))
+            .map(|i| cost(&unflat_index(i, n_levels)))
+            .collect();
+        CostGrid { n_levels, costs }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub(super) fn index(&self, n: [usize; DIM]) -> Option<usize> {
+        flat_index(n, self.n_levels)
+    }
+
+    pub(super) fn cost(&self, n: [usize; DIM]) -> Option<C> {
+        self.index(n).and_then(|i| self.costs[i])
+    }
+
+    // Stepping from `n` into `m`, None when either is blocked
+    pub(super) fn edge_cost(&self, n: [usize; DIM], m: [usize; DIM]) -> Option<C> {
+        self.cost(n)?;
+        self.cost(m)
+    }
+
+    // Set the cost of `cells`, returning those on the grid whose cost changed
+    pub(super) fn update_cells<I>(&mut self, cells: I, cost: Option<C>) -> Vec<[usize; DIM]>
+    where
+        I: IntoIterator<Item = [usize; DIM]>,
+    {
+        let mut changed = vec![];
+        for n in cells {
+            if let Some(i) = self.index(n)
+                && self.costs[i] != cost
+            {
+                self.costs[i] = cost;
+                changed.push(n);
+            }
+        }
+        changed
+    }
+}
+
+// Cells between the world-space corners `min` and `max`
+pub(super) fn region_cells<T, Q, const DIM: usize>(
+    quantizer: &Q,
+    min: [T; DIM],
+    max: [T; DIM],
+) -> Vec<[usize; DIM]>
+where
+    Q: HasQuantizationMethods<T, DIM>,
+{
+    let lo = quantizer.quantize(min);
+    let hi = quantizer.quantize(max);
+    wrapping_box_cells(lo, hi, quantizer.n_levels(), quantizer.periodic())
+}
+
+// The g and rhs values and the open queue shared by `LpaStar` and `DStarLite`.
+// The owner supplies the heuristic part of the keys, the queue is ordered by
+// (min(g, rhs) + h, min(g, rhs)) and stale entries are dropped lazily.
+#[derive(Debug, Clone)]
+pub(super) struct IncrementalSearch<C, const DIM: usize> {
+    n_levels: [usize; DIM],
+    g: Vec<Option<C>>,      // None is infinity
+    rhs: Vec<Option<C>>,
+    open: HashMap<[usize; DIM], Key<C>>,
+    queue: BinaryHeap<Reverse<(Key<C>, [usize; DIM])>>,
+}
+
+impl<C: Zero + Ord + Copy, const DIM: usize> IncrementalSearch<C, DIM> {
+    pub(super) fn new(n_levels: [usize; DIM]) -> Self {
+        IncrementalSearch {
+            n_levels,
+            g: vec![None; n_cells(n_levels)],
+            rhs: vec![None; n_cells(n_levels)],
+            open: HashMap::new(),
+            queue: BinaryHeap::new(),
+        }
+    }
+
+    pub(super) fn g(&self, n: [usize; DIM]) -> Option<C> {
+        flat_index(n, self.n_levels).and_then(|i| self.g[i])
+    }
+
+    pub(super) fn rhs(&self, n: [usize; DIM]) -> Option<C> {
+        flat_index(n, self.n_levels).and_then(|i| self.rhs[i])
+    }
+
+    pub(super) fn set_g(&mut self, n: [usize; DIM], g: Option<C>) {
+        if let Some(i) = flat_index(n, self.n_levels) {
+            self.g[i] = g;
+        }
+    }
+
+    pub(super) fn set_rhs(&mut self, n: [usize; DIM], rhs: Option<C>) {
+        if let Some(i) = flat_index(n, self.n_levels) {
+            self.rhs[i] = rhs;
+        }
+    }
+
+    // Finite and no longer changing, the stopping test of both searches
+    pub(super) fn is_settled(&self, n: [usize; DIM]) -> bool {
+        self.rhs(n).is_some() && self.rhs(n) == self.g(n)
+    }
+
+    // rhs < g, the cell got cheaper and g can take its rhs
+    pub(super) fn is_overconsistent(&self, n: [usize; DIM]) -> bool {
+        is_less(self.rhs(n), self.g(n))
+    }
+
+    // Key of a cell with a finite g or rhs, `h` is the heuristic part
+    pub(super) fn key(&self, n: [usize; DIM], h: C) -> Key<C> {
+        let m = match (self.g(n), self.rhs(n)) {
+            (Some(g), Some(rhs)) => g.min(rhs),
+            (Some(v), None) | (None, Some(v)) => v,
+            (None, None) => return (C::zero(), C::zero()),
+        };
+        (m + h, m)
+    }
+
+    pub(super) fn push(&mut self, n: [usize; DIM], key: Key<C>) {
+        self.open.insert(n, key);
+        self.queue.push(Reverse((key, n)));
+    }
+
+    // Queue `n` while g and rhs differ, drop it from the open set once they agree.
+    // The heuristic is only asked for cells that are queued.
+    pub(super) fn requeue<FH: FnOnce() -> C>(&mut self, n: [usize; DIM], h: FH) {
+        if self.g(n) != self.rhs(n) {
+            let key = self.key(n, h());
+            self.push(n, key);
+        } else {
+            self.open.remove(&n);
+        }
+    }
+
+    // Smallest key still in the open set, dropping stale entries
+    pub(super) fn top_key(&mut self) -> Option<Key<C>> {
+        while let Some(Reverse((key, n))) = self.queue.peek() {
+            if self.open.get(n) == Some(key) {
+                return Some(*key);
+            }
+            self.queue.pop();
+        }
+        None
+    }
+
+    // Remove the cell with the smallest key, with the key it was queued with
+    pub(super) fn pop(&mut self) -> Option<(Key<C>, [usize; DIM])> {
+        self.top_key()?;
+        let Reverse((key, n)) = self.queue.pop()?;
+        self.open.remove(&n);
+        Some((key, n))
+    }
+}
+
+// a < b where None is infinity
+fn is_less<C: Ord>(a: Option<C>, b: Option<C>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a < b,
+        (Some(_), None) => true,
+        _ => false,
+    }
+}
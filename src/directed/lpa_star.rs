@@ -0,0 +1,173 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::grid::grid_neighbors;
+use super::incremental::{region_cells, CostGrid, IncrementalSearch, Key};
+
+// Lifelong Planning A* (Koenig & Likhachev) between a fixed start and goal.
+// The g and rhs values survive between calls, so after `update_cells` only
+// the cells whose cost-from-start changed are searched again. Same cost model
+// as `DStarLite`: stepping into a cell costs that cell's cost, None blocks it,
+// and `heuristic(a, b)` must be consistent.
+pub struct LpaStar<T, C, Q, FH, const DIM: usize> {
+    quantizer: Q,
+    offsets: Vec<[isize; DIM]>,
+    heuristic: FH,
+    costs: CostGrid<C, DIM>,
+    search: IncrementalSearch<C, DIM>,
+    start: [usize; DIM],
+    goal: [usize; DIM],
+    _float: PhantomData<T>,
+}
+
+impl<T, C, Q, FH, const DIM: usize> LpaStar<T, C, Q, FH, DIM>
+where
+    T: FiniteFloat,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FH: FnMut(&[usize; DIM], &[usize; DIM]) -> C,
+{
+    pub fn new<FC>(
+        quantizer: Q,
+        start: [T; DIM],
+        goal: [T; DIM],
+        offsets: &[[isize; DIM]],
+        cost: FC,
+        heuristic: FH,
+    ) -> Self
+    where
+        FC: FnMut(&[usize; DIM]) -> Option<C>,
+    {
+        let n_levels = quantizer.n_levels();
+        let costs = CostGrid::new(n_levels, cost);
+        let start = quantizer.quantize(start);
+        let goal = quantizer.quantize(goal);
+        let mut planner = LpaStar {
+            quantizer,
+            offsets: offsets.to_vec(),
+            heuristic,
+            costs,
+            search: IncrementalSearch::new(n_levels),
+            start,
+            goal,
+            _float: PhantomData,
+        };
+        if planner.costs.index(start).is_some() {
+            planner.search.set_rhs(start, Some(C::zero()));
+            let key = planner.key(start);
+            planner.search.push(start, key);
+        }
+        planner
+    }
+
+    pub fn quantizer(&self) -> &Q {
+        &self.quantizer
+    }
+
+    pub fn start(&self) -> [usize; DIM] {
+        self.start
+    }
+
+    pub fn goal(&self) -> [usize; DIM] {
+        self.goal
+    }
+
+    pub fn cost(&self, n: [usize; DIM]) -> Option<C> {
+        self.costs.cost(n)
+    }
+
+    // Cost-from-start of the last search, None if unreachable or not yet searched
+    pub fn cost_from_start(&self, n: [usize; DIM]) -> Option<C> {
+        self.search.g(n)
+    }
+
+    // Change the cost of several cells, the search is repaired lazily
+    pub fn update_cells<I>(&mut self, cells: I, cost: Option<C>)
+    where
+        I: IntoIterator<Item = [usize; DIM]>,
+    {
+        // Edges into and out of a changed cell change
+        for n in self.costs.update_cells(cells, cost) {
+            self.update_vertex(n);
+            for m in grid_neighbors(&self.quantizer, n, &self.offsets) {
+                self.update_vertex(m);
+            }
+        }
+    }
+
+    // Change the cost of every cell between the world-space corners `min` and `max`
+    pub fn update_region(&mut self, min: [T; DIM], max: [T; DIM], cost: Option<C>) {
+        self.update_cells(region_cells(&self.quantizer, min, max), cost);
+    }
+
+    // Repair the search and return the dequantized path with its cost,
+    // None if the goal is unreachable
+    pub fn compute_shortest_path(&mut self) -> Option<(Vec<[T; DIM]>, C)> {
+        self.costs.index(self.goal)?;
+        while let Some(top) = self.search.top_key() {
+            // An unreachable goal is only settled once the queue runs dry
+            if self.search.is_settled(self.goal) && top >= self.key(self.goal) {
+                break;
+            }
+            let Some((_, u)) = self.search.pop() else {
+                break;
+            };
+            if self.search.is_overconsistent(u) {
+                self.search.set_g(u, self.search.rhs(u));
+            } else {
+                self.search.set_g(u, None);
+                self.update_vertex(u);
+            }
+            for s in grid_neighbors(&self.quantizer, u, &self.offsets) {
+                self.update_vertex(s);
+            }
+        }
+        self.path()
+    }
+
+    // Walk back from the goal through the cheapest predecessors,
+    // None when the start or the goal is blocked
+    fn path(&self) -> Option<(Vec<[T; DIM]>, C)> {
+        self.cost(self.start)?;
+        self.cost(self.goal)?;
+        let total = self.cost_from_start(self.goal)?;
+        let mut cells = vec![self.goal];
+        let mut n = self.goal;
+        while n != self.start {
+            if cells.len() > self.costs.len() {
+                return None;
+            }
+            n = grid_neighbors(&self.quantizer, n, &self.offsets).into_iter()
+                .filter_map(|p| Some((p, self.cost_from_start(p)? + self.costs.edge_cost(p, n)?)))
+                .min_by_key(|&(_, through)| through)
+                .map(|(p, _)| p)?;
+            cells.push(n);
+        }
+        let path = cells.into_iter().rev()
+            .map(|n| self.quantizer.dequantize(n))
+            .collect();
+        Some((path, total))
+    }
+
+    fn key(&mut self, n: [usize; DIM]) -> Key<C> {
+        let h = (self.heuristic)(&n, &self.goal);
+        self.search.key(n, h)
+    }
+
+    fn update_vertex(&mut self, u: [usize; DIM]) {
+        if self.costs.index(u).is_none() {
+            return;
+        }
+        if u != self.start {
+            let rhs = grid_neighbors(&self.quantizer, u, &self.offsets).into_iter()
+                .filter_map(|p| Some(self.cost_from_start(p)? + self.costs.edge_cost(p, u)?))
+                .min();
+            self.search.set_rhs(u, rhs);
+        }
+        let goal = self.goal;
+        let heuristic = &mut self.heuristic;
+        self.search.requeue(u, || heuristic(&u, &goal));
+    }
+}
@@ -1,3 +1,30 @@
+mod ara_star;
+mod cbs;
+mod continuous_astar;
+mod dstar_lite;
+mod flow_field;
+mod hierarchical;
+mod hybrid_astar;
+mod incremental;
+mod lpa_star;
+mod prioritized;
 mod quantized_astar;
+mod quantized_dijkstra;
+mod space_time_astar;
+mod terrain_astar;
+mod weighted_astar;
 
-pub use self::quantized_astar::*;
\ No newline at end of file
+pub use self::ara_star::*;
+pub use self::cbs::*;
+pub use self::continuous_astar::*;
+pub use self::dstar_lite::*;
+pub use self::flow_field::*;
+pub use self::hierarchical::*;
+pub use self::hybrid_astar::*;
+pub use self::lpa_star::*;
+pub use self::prioritized::*;
+pub use self::quantized_astar::*;
+pub use self::quantized_dijkstra::*;
+pub use self::space_time_astar::*;
+pub use self::terrain_astar::*;
+pub use self::weighted_astar::*;
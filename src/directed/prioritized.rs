@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::reservation_table::ReservationTable;
+use super::space_time_astar::{space_time_search, TimedPath};
+
+// Prioritized planning: agents are planned one after another in the order of
+// `agents`, each with space-time A* around the cells reserved by `table`, and
+// their paths are reserved in turn. Cheaper than `quantized_cbs` but neither
+// optimal nor complete. Agents not planned yet hold their starts, so earlier
+// agents route around them. An agent without a path gets None and stays put,
+// so its start is reserved from then on. Reservations already in `table`, ex.
+// from agents planned on earlier ticks, are respected.
+pub fn quantized_prioritized_planning<T, C, const DIM: usize, Q, FN, IN, FH>(
+    quantizer: &Q,
+    table: &mut ReservationTable<DIM>,
+    agents: &[([T; DIM], [T; DIM])],
+    horizon: usize,
+    wait_cost: C,
+    mut successors: FN,
+    mut heuristic: FH,
+) -> Vec<Option<(TimedPath<T, DIM>, C)>>
+where
+    T: FiniteFloat,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FN: FnMut(&[usize; DIM]) -> IN,
+    IN: IntoIterator<Item = ([usize; DIM], C)>,
+    FH: FnMut(&[usize; DIM], &[usize; DIM]) -> C,
+{
+    let cells: Vec<_> = agents.iter()
+        .map(|&(start, goal)| (quantizer.quantize(start), quantizer.quantize(goal)))
+        .collect();
+    // Agents still waiting for their turn, per start cell
+    let mut waiting: HashMap<[usize; DIM], usize> = HashMap::new();
+    for &(start, _) in &cells {
+        *waiting.entry(start).or_default() += 1;
+    }
+
+    cells.into_iter()
+        .map(|(start, goal)| {
+            if let Some(count) = waiting.get_mut(&start) {
+                *count -= 1;
+                if *count == 0 {
+                    waiting.remove(&start);
+                }
+            }
+            let result = space_time_search(
+                start,
+                0,
+                horizon,
+                wait_cost,
+                &mut successors,
+                |n| heuristic(n, &goal),
+                |n| *n == goal,
+                |from, to, t| table.is_move_blocked(*from, *to, t) || waiting.contains_key(to),
+            );
+            match result {
+                Some((path, cost)) => {
+                    table.reserve_path(&path);
+                    let timed = path.into_iter()
+                        .map(|(n, t)| (t, quantizer.dequantize(n)))
+                        .collect();
+                    Some((timed, cost))
+                }
+                None => {
+                    table.park(start, 0);
+                    None
+                }
+            }
+        })
+        .collect()
+}
@@ -1,5 +1,4 @@
 //use std::ops::Add;
-use std::hash::Hash;
 use std::fmt::Debug;
 use pathfinding::directed::astar::astar;
 use pathfinding::num_traits::Zero;
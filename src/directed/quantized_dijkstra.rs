@@ -0,0 +1,111 @@
+use std::fmt::Debug;
+use pathfinding::directed::dijkstra::dijkstra_all;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::grid::{flat_index, n_cells, unflat_index};
+use crate::utils::interpolation::wrapping_interpolate;
+
+// Cost to the nearest source for every quantized cell, None if unreachable
+#[derive(Debug, Clone)]
+pub struct DistanceField<C, const DIM: usize> {
+    pub n_levels: [usize; DIM],
+    costs: Vec<Option<C>>,
+    parents: Vec<Option<[usize; DIM]>>,
+}
+
+impl<C: Copy, const DIM: usize> DistanceField<C, DIM> {
+    pub fn get(&self, n: [usize; DIM]) -> Option<C> {
+        flat_index(n, self.n_levels).and_then(|i| self.costs[i])
+    }
+
+    // Previous cell on the cheapest path from a source, None for sources and unreachable cells
+    pub fn parent(&self, n: [usize; DIM]) -> Option<[usize; DIM]> {
+        flat_index(n, self.n_levels).and_then(|i| self.parents[i])
+    }
+
+    // Cost at the cell the float position falls into
+    pub fn sample<T, Q>(&self, quantizer: &Q, x: [T; DIM]) -> Option<C>
+    where
+        T: FiniteFloat,
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        self.get(quantizer.quantize(x))
+    }
+
+    // Cost blended from the surrounding cells, `to_float` converts the cost type
+    pub fn interpolate<T, Q, F>(&self, quantizer: &Q, x: [T; DIM], mut to_float: F) -> Option<T>
+    where
+        T: FiniteFloat + PartialOrd,
+        Q: HasQuantizationMethods<T, DIM>,
+        F: FnMut(C) -> T,
+    {
+        wrapping_interpolate(
+            quantizer.cell_coordinate(x),
+            self.n_levels,
+            quantizer.periodic(),
+            |n| self.get(n).map(&mut to_float),
+        )
+    }
+
+    // Reachable cells with their costs
+    pub fn iter(&self) -> impl Iterator<Item = ([usize; DIM], C)> + '_ {
+        self.costs.iter()
+            .enumerate()
+            .filter_map(|(i, cost)| cost.map(|c| (unflat_index(i, self.n_levels), c)))
+    }
+
+    // Reachable cells in world space with their costs
+    pub fn iter_dequantized<'a, T, Q>(
+        &'a self,
+        quantizer: &'a Q,
+    ) -> impl Iterator<Item = ([T; DIM], C)> + 'a
+    where
+        T: FiniteFloat,
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        self.iter().map(|(n, c)| (quantizer.dequantize(n), c))
+    }
+}
+
+// Multi-source Dijkstra over the whole quantized grid.
+// The successors are followed away from the sources, so to use the field as a
+// heuristic towards a goal the closure should yield the reversed edges (the
+// same edges on an undirected grid).
+pub fn quantized_dijkstra_field<T, C, const DIM: usize, Q, IS, FN, IN>(
+    quantizer: &Q,
+    sources: IS,
+    mut successors: FN,
+) -> DistanceField<C, DIM>
+where
+    T: FiniteFloat,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    IS: IntoIterator<Item = [T; DIM]>,
+    FN: FnMut(&[usize; DIM]) -> IN,
+    IN: IntoIterator<Item = ([usize; DIM], C)>,
+{
+    let n_levels = quantizer.n_levels();
+    let sources_n: Vec<[usize; DIM]> = sources.into_iter()
+        .map(|x| quantizer.quantize(x))
+        .filter(|&n| flat_index(n, n_levels).is_some())
+        .collect();
+
+    // None is a virtual node linked to every source at zero cost
+    let parents = dijkstra_all(&None, |node: &Option<[usize; DIM]>| -> Vec<(Option<[usize; DIM]>, C)> {
+        match node {
+            None => sources_n.iter().map(|&n| (Some(n), C::zero())).collect(),
+            Some(n) => successors(n).into_iter().map(|(m, c)| (Some(m), c)).collect(),
+        }
+    });
+
+    let mut costs = vec![None; n_cells(n_levels)];
+    let mut parent_cells = vec![None; n_cells(n_levels)];
+    for (node, (parent, cost)) in parents {
+        if let Some(i) = node.and_then(|n| flat_index(n, n_levels)) {
+            costs[i] = Some(cost);
+            parent_cells[i] = parent;
+        }
+    }
+    DistanceField { n_levels, costs, parents: parent_cells }
+}
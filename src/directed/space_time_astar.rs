@@ -0,0 +1,191 @@
+use std::array::from_fn;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use pathfinding::directed::astar::astar;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::grid::wrapping_box_cells;
+
+// Waypoints with the time step they are reached at
+pub type TimedPath<T, const DIM: usize> = Vec<(usize, [T; DIM])>;
+
+// Cells with the time step they are occupied at
+pub(crate) type CellTimes<const DIM: usize> = Vec<([usize; DIM], usize)>;
+
+// Axis-aligned box obstacle moving with a constant velocity per time step,
+// present from `from` until `until` (inclusive) when set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovingRegion<T, const DIM: usize> {
+    pub min: [T; DIM],
+    pub max: [T; DIM],
+    pub velocity: [T; DIM],
+    pub from: usize,
+    pub until: Option<usize>,
+}
+
+impl<T: FiniteFloat, const DIM: usize> MovingRegion<T, DIM> {
+    // Static box present at every time step
+    pub fn new(min: [T; DIM], max: [T; DIM]) -> Self {
+        MovingRegion {
+            min,
+            max,
+            velocity: [T::from_usize(0); DIM],
+            from: 0,
+            until: None,
+        }
+    }
+
+    pub fn with_velocity(mut self, velocity: [T; DIM]) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    pub fn with_interval(mut self, from: usize, until: usize) -> Self {
+        self.from = from;
+        self.until = Some(until);
+        self
+    }
+
+    // Corners at time step `t`, None while absent
+    pub fn at(&self, t: usize) -> Option<([T; DIM], [T; DIM])> {
+        if t < self.from || self.until.is_some_and(|until| t > until) {
+            return None;
+        }
+        let elapsed = T::from_usize(t - self.from);
+        let min = from_fn(|i| self.min[i] + self.velocity[i] * elapsed);
+        let max = from_fn(|i| self.max[i] + self.velocity[i] * elapsed);
+        Some((min, max))
+    }
+
+    // Cells covered at time step `t`, none once the box has left the grid.
+    // Level k spans cell coordinates [k - 0.5, k + 0.5), a box past level 0
+    // would otherwise be clamped onto it when quantized. Along periodic axes
+    // the box runs from `min` up to `max` and may cross the seam.
+    pub fn cells<Q>(&self, quantizer: &Q, t: usize) -> Vec<[usize; DIM]>
+    where
+        T: PartialOrd,
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        let Some((min, max)) = self.at(t) else {
+            return vec![];
+        };
+        let lo = quantizer.cell_coordinate(min);
+        let hi = quantizer.cell_coordinate(max);
+        let n_levels = quantizer.n_levels();
+        let periodic = quantizer.periodic();
+        let half = T::from_usize(1) / T::from_usize(2);
+        let outside = (0..DIM).any(|i| {
+            let below = hi[i] + half < T::from_usize(0);
+            let above = lo[i] + half >= T::from_usize(n_levels[i]);
+            !periodic[i] && (below || above)
+        });
+        if outside {
+            return vec![];
+        }
+        wrapping_box_cells(quantizer.quantize(min), quantizer.quantize(max), n_levels, periodic)
+    }
+}
+
+// A* over (cell, time step) up to `horizon`, with waiting in place as an
+// action. `blocked(from, to, t)` refuses the move that ends in `to` at `t`.
+// A goal only counts if the agent can stay there until the horizon.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn space_time_search<C, const DIM: usize, FN, IN, FH, FS, FB>(
+    start: [usize; DIM],
+    start_time: usize,
+    horizon: usize,
+    wait_cost: C,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+    blocked: FB,
+) -> Option<(CellTimes<DIM>, C)>
+where
+    C: Zero + Ord + Copy + Debug,
+    FN: FnMut(&[usize; DIM]) -> IN,
+    IN: IntoIterator<Item = ([usize; DIM], C)>,
+    FH: FnMut(&[usize; DIM]) -> C,
+    FS: FnMut(&[usize; DIM]) -> bool,
+    FB: FnMut(&[usize; DIM], &[usize; DIM], usize) -> bool,
+{
+    // Both the expansion and the goal test ask for blocked moves
+    let blocked = RefCell::new(blocked);
+    let blocked = |from: &[usize; DIM], to: &[usize; DIM], t: usize| {
+        (blocked.borrow_mut())(from, to, t)
+    };
+    if start_time > horizon || blocked(&start, &start, start_time) {
+        return None;
+    }
+    let mut staying: HashMap<([usize; DIM], usize), bool> = HashMap::new();
+    astar(
+        &(start, start_time),
+        |&(n, t)| {
+            if t >= horizon {
+                return vec![];
+            }
+            let mut next: Vec<_> = successors(&n).into_iter()
+                .filter(|(m, _)| !blocked(&n, m, t + 1))
+                .map(|(m, c)| ((m, t + 1), c))
+                .collect();
+            if !blocked(&n, &n, t + 1) {
+                next.push(((n, t + 1), wait_cost));
+            }
+            next
+        },
+        |(n, _)| heuristic(n),
+        |&(n, t)| {
+            success(&n) && *staying.entry((n, t))
+                .or_insert_with(|| (t + 1..=horizon).all(|s| !blocked(&n, &n, s)))
+        },
+    )
+}
+
+// Space-time A*: plans around obstacles that move, with waiting as an extra
+// action costing `wait_cost`. Time advances one step per move and the search
+// gives up past `horizon`. Returns timed waypoints (t, x).
+#[allow(clippy::too_many_arguments)]
+pub fn quantized_space_time_astar<T, C, const DIM: usize, Q, FN, IN, FH, FS>(
+    quantizer: &Q,
+    start: [T; DIM],
+    obstacles: &[MovingRegion<T, DIM>],
+    horizon: usize,
+    wait_cost: C,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+) -> Option<(TimedPath<T, DIM>, C)>
+where
+    T: FiniteFloat + PartialOrd,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FN: FnMut(&[usize; DIM]) -> IN,
+    IN: IntoIterator<Item = ([usize; DIM], C)>,
+    FH: FnMut(&[usize; DIM]) -> C,
+    FS: FnMut(&[usize; DIM]) -> bool,
+{
+    let mut occupied: HashMap<usize, HashSet<[usize; DIM]>> = HashMap::new();
+    let (path, cost) = space_time_search(
+        quantizer.quantize(start),
+        0,
+        horizon,
+        wait_cost,
+        successors,
+        heuristic,
+        success,
+        |from, to, t| {
+            let mut occupied_at = |t: usize, n: &[usize; DIM]| {
+                occupied.entry(t)
+                    .or_insert_with(|| obstacles.iter().flat_map(|o| o.cells(quantizer, t)).collect())
+                    .contains(n)
+            };
+            // Entering an occupied cell, or swapping places with an obstacle
+            occupied_at(t, to) || (from != to && occupied_at(t, from) && occupied_at(t - 1, to))
+        },
+    )?;
+    let timed = path.into_iter()
+        .map(|(n, t)| (t, quantizer.dequantize(n)))
+        .collect();
+    Some((timed, cost))
+}
@@ -0,0 +1,26 @@
+use crate::directed::quantized_astar;
+use crate::traits::{HasQuantizationMethods, TranscendentalFloat};
+use crate::utils::terrain::Heightmap;
+
+// `quantized_astar` over a heightmap with its slope-aware costs, the
+// dequantized waypoints are lifted onto the terrain as (x, y, z)
+pub fn quantized_terrain_astar<T>(
+    heightmap: &Heightmap<T>,
+    start: [T; 2],
+    goal: [T; 2],
+    offsets: &[[isize; 2]],
+) -> Option<(Vec<[T; 3]>, u32)>
+where
+    T: TranscendentalFloat,
+{
+    let quantizer = heightmap.quantizer();
+    let goal_n = quantizer.quantize(goal);
+    let (path, cost) = quantized_astar(
+        quantizer,
+        start,
+        |n| heightmap.successors(n, offsets),
+        |n| heightmap.heuristic(*n, goal_n),
+        |n| *n == goal_n,
+    )?;
+    Some((heightmap.lift(&path), cost))
+}
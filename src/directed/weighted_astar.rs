@@ -0,0 +1,150 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::ops::Bound::{Excluded, Unbounded};
+use std::fmt::Debug;
+use pathfinding::num_traits::Zero;
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use super::AraStar;
+
+// A* with the heuristic inflated to g + (1 + ε)·h. With a consistent
+// heuristic the path costs at most (1 + ε) times the optimal cost, usually
+// after far fewer expansions. Same as the first round of `AraStar`, which
+// never reopens expanded cells, so admissible alone is not enough.
+pub fn quantized_weighted_astar<T, C, const DIM: usize, Q, FN, IN, FH, FS, FF>(
+    quantizer: &Q,
+    start: [T; DIM],
+    epsilon: T,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+    to_float: FF,
+) -> Option<(Vec<[T; DIM]>, C)>
+where
+    T: FiniteFloat + PartialOrd,
+    C: Zero + Ord + Copy + Debug,
+    Q: HasQuantizationMethods<T, DIM>,
+    FN: FnMut(&[usize; DIM]) -> IN,
+    IN: IntoIterator<Item = ([usize; DIM], C)>,
+    FH: FnMut(&[usize; DIM]) -> C,
+    FS: FnMut(&[usize; DIM]) -> bool,
+    FF: FnMut(C) -> T,
+{
+    AraStar::new(quantizer, start, successors, heuristic, success, to_float)
+        .with_inflation(T::from_usize(1) + epsilon, T::from_usize(1))
+        .next()
+        .map(|solution| (solution.path, solution.cost))
+}
+
+// Focal search (A*ε, Pearl & Kim). Open cells are ordered by g + h, and among
+// those within (1 + ε) of the smallest the one with the lowest `focal`
+// estimate is expanded, ex. the remaining distance or a count of conflicts.
+// With an admissible heuristic the path costs at most (1 + ε) times optimal.
+#[allow(clippy::too_many_arguments)]
+pub fn quantized_focal_astar<T, C, K, const DIM: usize, Q, FN, IN, FH, FFO, FS, FF>(
+    quantizer: &Q,
+    start: [T; DIM],
+    epsilon: T,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut focal: FFO,
+    mut success: FS,
+    mut to_float: FF,
+) -> Option<(Vec<[T; DIM]>, C)>
+where
+    T: FiniteFloat + PartialOrd,
+    C: Zero + Ord + Copy + Debug,
+    K: Ord,
+    Q: HasQuantizationMethods<T, DIM>,
+    FN: FnMut(&[usize; DIM]) -> IN,
+    IN: IntoIterator<Item = ([usize; DIM], C)>,
+    FH: FnMut(&[usize; DIM]) -> C,
+    FFO: FnMut(&[usize; DIM]) -> K,
+    FS: FnMut(&[usize; DIM]) -> bool,
+    FF: FnMut(C) -> T,
+{
+    let start = quantizer.quantize(start);
+    let mut g: HashMap<[usize; DIM], (C, Option<[usize; DIM]>)> = HashMap::new();
+    let mut f: HashMap<[usize; DIM], C> = HashMap::new();
+    let mut open: BTreeSet<(C, [usize; DIM])> = BTreeSet::new();
+    // Open cells within the limit, by focal value then f. Entries of cells
+    // that were expanded, reached more cheaply or dropped are skipped lazily.
+    let mut focal_queue: BinaryHeap<Reverse<(K, C, [usize; DIM])>> = BinaryHeap::new();
+    let mut in_focal: HashSet<[usize; DIM]> = HashSet::new();
+    // Every open entry up to `swept` is in FOCAL
+    let mut swept: Option<(C, [usize; DIM])> = None;
+    g.insert(start, (C::zero(), None));
+    f.insert(start, heuristic(&start));
+    open.insert((f[&start], start));
+
+    let factor = T::from_usize(1) + epsilon;
+    while let Some(&(f_min, _)) = open.first() {
+        let limit = factor * to_float(f_min);
+
+        // Move the cells the limit now covers into FOCAL
+        let from = swept.map_or(Unbounded, Excluded);
+        for &(f_m, m) in open.range((from, Unbounded)) {
+            if to_float(f_m) > limit {
+                break;
+            }
+            swept = Some((f_m, m));
+            if in_focal.insert(m) {
+                focal_queue.push(Reverse((focal(&m), f_m, m)));
+            }
+        }
+
+        let mut next = None;
+        while let Some(Reverse((_, f_m, m))) = focal_queue.pop() {
+            if f.get(&m) != Some(&f_m) || !in_focal.contains(&m) {
+                continue;
+            }
+            if to_float(f_m) > limit {
+                // f_min went down, back to OPEN until the limit covers it again
+                in_focal.remove(&m);
+                if swept.is_some_and(|key| (f_m, m) <= key) {
+                    swept = open.range(..(f_m, m)).next_back().copied();
+                }
+                continue;
+            }
+            next = Some((f_m, m));
+            break;
+        }
+        let (f_n, n) = next.unwrap_or((f_min, open.first().unwrap().1));
+        open.remove(&(f_n, n));
+        f.remove(&n);
+        in_focal.remove(&n);
+
+        if success(&n) {
+            let cost = g[&n].0;
+            let mut path = vec![];
+            let mut current = Some(n);
+            while let Some(m) = current {
+                path.push(quantizer.dequantize(m));
+                current = g[&m].1;
+            }
+            path.reverse();
+            return Some((path, cost));
+        }
+        let g_n = g[&n].0;
+        for (m, step_cost) in successors(&n) {
+            let next_g = g_n + step_cost;
+            if g.get(&m).is_some_and(|&(old, _)| old <= next_g) {
+                continue;
+            }
+            // Cheaper paths reopen expanded cells too, which keeps the bound
+            g.insert(m, (next_g, Some(n)));
+            if let Some(old_f) = f.remove(&m) {
+                open.remove(&(old_f, m));
+            }
+            in_focal.remove(&m);
+            let f_m = next_g + heuristic(&m);
+            f.insert(m, f_m);
+            open.insert((f_m, m));
+            if swept.is_some_and(|key| (f_m, m) <= key) {
+                in_focal.insert(m);
+                focal_queue.push(Reverse((focal(&m), f_m, m)));
+            }
+        }
+    }
+    None
+}
@@ -7,6 +7,72 @@ pub trait HasQuantizationMethods<T, const DIM: usize> {
     fn quantize_ieee754(&self, x: [T; DIM]) -> [usize; DIM];
     fn quantize(&self, x: [T; DIM]) -> [usize; DIM];
     fn dequantize(&self, n: [usize; DIM]) -> [T; DIM];
+    // Number of levels per axis, the extent of the quantized grid
+    fn n_levels(&self) -> [usize; DIM];
+    // Continuous cell coordinate before any rounding, ex. (x - a) / step_size
+    fn cell_coordinate(&self, x: [T; DIM]) -> [T; DIM];
+
+    // Axes whose last level is next to the first one
+    fn periodic(&self) -> [bool; DIM] {
+        [false; DIM]
+    }
+
+    // World-space offset from `from` to `to`, the shorter way around periodic axes
+    fn wrapped_difference(&self, from: [T; DIM], to: [T; DIM]) -> [T; DIM]
+    where
+        T: FiniteFloat,
+    {
+        std::array::from_fn(|i| to[i] - from[i])
+    }
+
+    // Offset of `x` from the level it quantizes to, in world space
+    fn residual(&self, x: [T; DIM]) -> [T; DIM]
+    where
+        T: FiniteFloat,
+    {
+        self.wrapped_difference(self.dequantize(self.quantize(x)), x)
+    }
+}
+
+// Planners that own their quantizer can also borrow one
+impl<T, const DIM: usize, Q: HasQuantizationMethods<T, DIM> + ?Sized> HasQuantizationMethods<T, DIM> for &Q {
+    fn quantize_ieee754(&self, x: [T; DIM]) -> [usize; DIM] {
+        (**self).quantize_ieee754(x)
+    }
+
+    fn quantize(&self, x: [T; DIM]) -> [usize; DIM] {
+        (**self).quantize(x)
+    }
+
+    fn dequantize(&self, n: [usize; DIM]) -> [T; DIM] {
+        (**self).dequantize(n)
+    }
+
+    fn n_levels(&self) -> [usize; DIM] {
+        (**self).n_levels()
+    }
+
+    fn cell_coordinate(&self, x: [T; DIM]) -> [T; DIM] {
+        (**self).cell_coordinate(x)
+    }
+
+    fn periodic(&self) -> [bool; DIM] {
+        (**self).periodic()
+    }
+
+    fn wrapped_difference(&self, from: [T; DIM], to: [T; DIM]) -> [T; DIM]
+    where
+        T: FiniteFloat,
+    {
+        (**self).wrapped_difference(from, to)
+    }
+
+    fn residual(&self, x: [T; DIM]) -> [T; DIM]
+    where
+        T: FiniteFloat,
+    {
+        (**self).residual(x)
+    }
 }
 
 // Loosen float. Looser than IEEE 754 since it is to work with the quantizer
@@ -24,6 +90,7 @@ pub trait FiniteFloat:
     // for quantization or similar operation
     fn round_ieee754 (self) -> usize;
     fn round(self) -> Self;
+    fn floor(self) -> Self;
 }
 
 impl FiniteFloat for f32 {
@@ -43,6 +110,10 @@ impl FiniteFloat for f32 {
     fn round(self) -> Self {
         self.round()
     }
+
+    fn floor(self) -> Self {
+        self.floor()
+    }
 }
 
 impl FiniteFloat for f64 {
@@ -62,4 +133,100 @@ impl FiniteFloat for f64 {
     fn round(self) -> Self {
         self.round()
     }
+
+    fn floor(self) -> Self {
+        self.floor()
+    }
+}
+
+
+// For the non-linear quantizers, on top of the arithmetic of FiniteFloat
+pub trait TranscendentalFloat: FiniteFloat + PartialOrd {
+    fn ln(self) -> Self;
+    fn exp(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn sqrt(self) -> Self;
+
+    // trigonometry, for the angular quantizers
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn asin(self) -> Self;
+    fn atan2(self, other: Self) -> Self;
+    fn pi() -> Self;
+}
+
+impl TranscendentalFloat for f32 {
+    fn ln(self) -> Self {
+        self.ln()
+    }
+
+    fn exp(self) -> Self {
+        self.exp()
+    }
+
+    fn powf(self, n: Self) -> Self {
+        self.powf(n)
+    }
+
+    fn sqrt(self) -> Self {
+        self.sqrt()
+    }
+
+    fn sin(self) -> Self {
+        self.sin()
+    }
+
+    fn cos(self) -> Self {
+        self.cos()
+    }
+
+    fn asin(self) -> Self {
+        self.asin()
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        self.atan2(other)
+    }
+
+    fn pi() -> Self {
+        std::f32::consts::PI
+    }
+}
+
+impl TranscendentalFloat for f64 {
+    fn ln(self) -> Self {
+        self.ln()
+    }
+
+    fn exp(self) -> Self {
+        self.exp()
+    }
+
+    fn powf(self, n: Self) -> Self {
+        self.powf(n)
+    }
+
+    fn sqrt(self) -> Self {
+        self.sqrt()
+    }
+
+    fn sin(self) -> Self {
+        self.sin()
+    }
+
+    fn cos(self) -> Self {
+        self.cos()
+    }
+
+    fn asin(self) -> Self {
+        self.asin()
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        self.atan2(other)
+    }
+
+    fn pi() -> Self {
+        std::f64::consts::PI
+    }
 }
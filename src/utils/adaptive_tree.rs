@@ -0,0 +1,238 @@
+use std::array::from_fn;
+use std::collections::{HashMap, VecDeque};
+
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::quantizer::Quantizer;
+
+// A cube of size^DIM fine cells, all free or all blocked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Leaf<const DIM: usize> {
+    pub corner: [usize; DIM],
+    pub size: usize,
+    pub free: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Node {
+    Leaf(usize),   // index into leaves
+    Branch(usize), // index of the first of 2^DIM consecutive children
+}
+
+// Classification before flattening, uniform children are merged
+enum Region {
+    Uniform(bool),
+    Split(Vec<Region>),
+}
+
+// Adaptive 2^DIM-tree over the cells of a fine quantizer, a quadtree in 2D
+// and an octree in 3D. Cubes are only subdivided where free and blocked cells
+// mix, so open areas become a few large leaves. Leaves are named by their
+// lowest fine cell, which makes the tree a quantizer of its own: `quantize`
+// gives the leaf containing a point and `dequantize` its center, so it can be
+// handed to `quantized_astar` together with `successors`.
+#[derive(Debug, Clone)]
+pub struct AdaptiveTree<T: FiniteFloat, const DIM: usize> {
+    fine: Quantizer<T, DIM>,
+    size: usize,
+    nodes: Vec<Node>,
+    leaves: Vec<Leaf<DIM>>,
+    corners: HashMap<[usize; DIM], usize>,
+    adjacency: Vec<Vec<usize>>,
+}
+
+impl<T: FiniteFloat, const DIM: usize> AdaptiveTree<T, DIM> {
+    // `blocked` is asked once per fine cell, cells off the grid count as blocked
+    pub fn new<FB>(fine: Quantizer<T, DIM>, mut blocked: FB) -> Self
+    where
+        FB: FnMut(&[usize; DIM]) -> bool,
+    {
+        let n_levels = fine.n_levels;
+        let size = n_levels.iter().copied().max().unwrap_or(1).max(1).next_power_of_two();
+        let mut is_free = |n: &[usize; DIM]| (0..DIM).all(|i| n[i] < n_levels[i]) && !blocked(n);
+        let root = classify([0; DIM], size, n_levels, &mut is_free);
+
+        let mut tree = AdaptiveTree {
+            fine,
+            size,
+            nodes: vec![Node::Leaf(0)],
+            leaves: vec![],
+            corners: HashMap::new(),
+            adjacency: vec![],
+        };
+        tree.flatten(root);
+        tree.adjacency = (0..tree.leaves.len()).map(|l| tree.find_neighbors(l)).collect();
+        tree
+    }
+
+    // Lay the regions out breadth first so that siblings are consecutive
+    fn flatten(&mut self, root: Region) {
+        let mut queue = VecDeque::from([(0, root, [0; DIM], self.size)]);
+        while let Some((index, region, corner, size)) = queue.pop_front() {
+            match region {
+                Region::Uniform(free) => {
+                    self.nodes[index] = Node::Leaf(self.leaves.len());
+                    self.corners.insert(corner, self.leaves.len());
+                    self.leaves.push(Leaf { corner, size, free });
+                }
+                Region::Split(children) => {
+                    let first = self.nodes.len();
+                    self.nodes[index] = Node::Branch(first);
+                    self.nodes.extend(children.iter().map(|_| Node::Leaf(0)));
+                    let half = size / 2;
+                    for (k, child) in children.into_iter().enumerate() {
+                        queue.push_back((first + k, child, child_corner(corner, half, k), half));
+                    }
+                }
+            }
+        }
+    }
+
+    // Free leaves sharing a face with leaf `l`
+    fn find_neighbors(&self, l: usize) -> Vec<usize> {
+        let leaf = self.leaves[l];
+        let n_levels = self.fine.n_levels;
+        let mut neighbors = vec![];
+        if !leaf.free {
+            return neighbors;
+        }
+        for axis in 0..DIM {
+            let outside = [
+                leaf.corner[axis].checked_sub(1),
+                Some(leaf.corner[axis] + leaf.size).filter(|&v| v < n_levels[axis]),
+            ];
+            for v in outside.into_iter().flatten() {
+                let extent: [usize; DIM] = from_fn(|i| {
+                    if i == axis { 1 } else { leaf.size.min(n_levels[i].saturating_sub(leaf.corner[i])) }
+                });
+                for k in 0..extent.iter().product::<usize>() {
+                    let mut rest = k;
+                    let cell: [usize; DIM] = from_fn(|i| {
+                        let offset = rest % extent[i];
+                        rest /= extent[i];
+                        if i == axis { v } else { leaf.corner[i] + offset }
+                    });
+                    let m = self.locate(cell);
+                    if self.leaves[m].free && !neighbors.contains(&m) {
+                        neighbors.push(m);
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+
+    // Leaf containing the fine cell `n`
+    fn locate(&self, n: [usize; DIM]) -> usize {
+        let mut index = 0;
+        let mut corner = [0; DIM];
+        let mut size = self.size;
+        loop {
+            match self.nodes[index] {
+                Node::Leaf(l) => return l,
+                Node::Branch(first) => {
+                    size /= 2;
+                    let k = (0..DIM)
+                        .filter(|&i| n[i].min(self.size - 1) >= corner[i] + size)
+                        .fold(0, |k, i| k | 1 << i);
+                    corner = child_corner(corner, size, k);
+                    index = first + k;
+                }
+            }
+        }
+    }
+
+    pub fn leaves(&self) -> &[Leaf<DIM>] {
+        &self.leaves
+    }
+
+    // The leaf containing the fine cell `n`
+    pub fn leaf(&self, n: [usize; DIM]) -> Leaf<DIM> {
+        self.leaves[self.locate(n)]
+    }
+
+    pub fn is_free(&self, n: [usize; DIM]) -> bool {
+        self.leaf(n).free
+    }
+
+    // Free leaves sharing a face with the leaf containing `n`
+    pub fn neighbors(&self, n: &[usize; DIM]) -> Vec<[usize; DIM]> {
+        self.adjacency[self.locate(*n)].iter()
+            .map(|&m| self.leaves[m].corner)
+            .collect()
+    }
+
+    // Successors for `quantized_astar`, `cost` is given both leaf centers
+    pub fn successors<C, FC>(&self, n: &[usize; DIM], mut cost: FC) -> Vec<([usize; DIM], C)>
+    where
+        FC: FnMut(&[T; DIM], &[T; DIM]) -> C,
+    {
+        let from = self.dequantize(*n);
+        self.neighbors(n).into_iter()
+            .map(|m| {
+                let to = self.dequantize(m);
+                (m, cost(&from, &to))
+            })
+            .collect()
+    }
+}
+
+fn child_corner<const DIM: usize>(corner: [usize; DIM], half: usize, k: usize) -> [usize; DIM] {
+    from_fn(|i| corner[i] + if k >> i & 1 == 1 { half } else { 0 })
+}
+
+fn classify<const DIM: usize, F>(
+    corner: [usize; DIM],
+    size: usize,
+    n_levels: [usize; DIM],
+    is_free: &mut F,
+) -> Region
+where
+    F: FnMut(&[usize; DIM]) -> bool,
+{
+    // Padding past the grid, no need to visit its cells
+    if (0..DIM).any(|i| corner[i] >= n_levels[i]) {
+        return Region::Uniform(false);
+    }
+    if size == 1 {
+        return Region::Uniform(is_free(&corner));
+    }
+    let half = size / 2;
+    let children: Vec<Region> = (0..1usize << DIM)
+        .map(|k| classify(child_corner(corner, half, k), half, n_levels, is_free))
+        .collect();
+    match children[0] {
+        Region::Uniform(first)
+            if children.iter().all(|c| matches!(c, Region::Uniform(f) if *f == first)) =>
+        {
+            Region::Uniform(first)
+        }
+        _ => Region::Split(children),
+    }
+}
+
+impl<T: FiniteFloat, const DIM: usize> HasQuantizationMethods<T, DIM> for AdaptiveTree<T, DIM> {
+    fn quantize_ieee754(&self, x: [T; DIM]) -> [usize; DIM] {
+        self.leaf(self.fine.quantize_ieee754(x)).corner
+    }
+
+    fn quantize(&self, x: [T; DIM]) -> [usize; DIM] {
+        self.leaf(self.fine.quantize(x)).corner
+    }
+
+    // Center of the leaf
+    fn dequantize(&self, n: [usize; DIM]) -> [T; DIM] {
+        let leaf = self.leaf(n);
+        let corner = self.fine.dequantize(leaf.corner);
+        let step = self.fine.step_size();
+        let half_extent = T::from_usize(leaf.size - 1) / T::from_usize(2);
+        from_fn(|i| corner[i] + half_extent * step[i])
+    }
+
+    fn n_levels(&self) -> [usize; DIM] {
+        self.fine.n_levels
+    }
+
+    fn cell_coordinate(&self, x: [T; DIM]) -> [T; DIM] {
+        self.fine.cell_coordinate(x)
+    }
+}
@@ -0,0 +1,126 @@
+use crate::traits::{HasQuantizationMethods, FiniteFloat, TranscendentalFloat};
+use crate::utils::float::abs;
+use crate::utils::quantizer::Quantizer;
+use std::array::from_fn;
+
+// Uniform quantization in a local frame, world = linear * local + translation.
+// The inner quantizer's bounds are given in the local frame, so a rotated or
+// sheared grid is just an axis-aligned one seen through the transform.
+#[derive(Debug, Clone, Copy)]
+pub struct AffineQuantizer<T: FiniteFloat + PartialOrd, const DIM: usize> {
+    local: Quantizer<T, DIM>,
+    linear: [[T; DIM]; DIM],
+    inverse: [[T; DIM]; DIM],
+    translation: [T; DIM],
+}
+
+fn mul<T: FiniteFloat, const DIM: usize>(m: &[[T; DIM]; DIM], x: [T; DIM]) -> [T; DIM] {
+    from_fn(|i| {
+        (0..DIM).fold(T::from_usize(0), |sum, j| sum + m[i][j] * x[j])
+    })
+}
+
+// Gauss-Jordan elimination with partial pivoting, None if singular
+fn invert<T: FiniteFloat + PartialOrd, const DIM: usize>(m: [[T; DIM]; DIM]) -> Option<[[T; DIM]; DIM]> {
+    let zero = T::from_usize(0);
+    let mut a = m;
+    let mut inv: [[T; DIM]; DIM] = from_fn(|i| from_fn(|j| T::from_usize((i == j) as usize)));
+    for col in 0..DIM {
+        let pivot = (col..DIM).max_by(|&r, &s| {
+            abs(a[r][col]).partial_cmp(&abs(a[s][col])).unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+        if abs(a[pivot][col]) <= zero {
+            return None;
+        }
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+
+        let p = a[col][col];
+        for j in 0..DIM {
+            a[col][j] = a[col][j] / p;
+            inv[col][j] = inv[col][j] / p;
+        }
+        for row in 0..DIM {
+            if row == col {
+                continue;
+            }
+            let factor = a[row][col];
+            for j in 0..DIM {
+                a[row][j] = a[row][j] - factor * a[col][j];
+                inv[row][j] = inv[row][j] - factor * inv[col][j];
+            }
+        }
+    }
+    Some(inv)
+}
+
+impl<T: FiniteFloat + PartialOrd, const DIM: usize> AffineQuantizer<T, DIM> {
+    // Panics if `linear` is singular
+    pub fn new(local: Quantizer<T, DIM>, linear: [[T; DIM]; DIM], translation: [T; DIM]) -> Self {
+        let inverse = invert(linear).expect("linear part of the frame must be invertible");
+        AffineQuantizer { local, linear, inverse, translation }
+    }
+
+    // Only shifted, ex. a local robot frame without rotation
+    pub fn translated(local: Quantizer<T, DIM>, translation: [T; DIM]) -> Self {
+        let identity = from_fn(|i| from_fn(|j| T::from_usize((i == j) as usize)));
+        Self::new(local, identity, translation)
+    }
+
+    pub fn to_local(&self, x: [T; DIM]) -> [T; DIM] {
+        mul(&self.inverse, from_fn(|i| x[i] - self.translation[i]))
+    }
+
+    pub fn to_world(&self, x: [T; DIM]) -> [T; DIM] {
+        let y = mul(&self.linear, x);
+        from_fn(|i| y[i] + self.translation[i])
+    }
+
+    pub fn local_quantizer(&self) -> &Quantizer<T, DIM> {
+        &self.local
+    }
+}
+
+impl<T: TranscendentalFloat> AffineQuantizer<T, 2> {
+    // Grid rotated counterclockwise by `angle` (radians) around `translation`
+    pub fn rotated(local: Quantizer<T, 2>, angle: T, translation: [T; 2]) -> Self {
+        let (sin, cos) = (angle.sin(), angle.cos());
+        let linear = [
+            [cos, T::from_usize(0) - sin],
+            [sin, cos],
+        ];
+        Self::new(local, linear, translation)
+    }
+}
+
+impl<T: FiniteFloat + PartialOrd, const DIM: usize> HasQuantizationMethods<T, DIM> for AffineQuantizer<T, DIM> {
+    fn quantize_ieee754(&self, x: [T; DIM]) -> [usize; DIM] {
+        self.local.quantize_ieee754(self.to_local(x))
+    }
+
+    fn quantize(&self, x: [T; DIM]) -> [usize; DIM] {
+        self.local.quantize(self.to_local(x))
+    }
+
+    fn dequantize(&self, n: [usize; DIM]) -> [T; DIM] {
+        self.to_world(self.local.dequantize(n))
+    }
+
+    fn n_levels(&self) -> [usize; DIM] {
+        self.local.n_levels
+    }
+
+    fn cell_coordinate(&self, x: [T; DIM]) -> [T; DIM] {
+        self.local.cell_coordinate(self.to_local(x))
+    }
+
+    fn periodic(&self) -> [bool; DIM] {
+        self.local.periodic()
+    }
+
+    // Wrapped in the local frame, then mapped back without the translation
+    fn wrapped_difference(&self, from: [T; DIM], to: [T; DIM]) -> [T; DIM] {
+        let d = self.local.wrapped_difference(self.to_local(from), self.to_local(to));
+        mul(&self.linear, d)
+    }
+}
@@ -0,0 +1,69 @@
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use std::array::from_fn;
+
+// Non-uniform quantizer. Each axis is split by sorted edges
+// e_0 < e_1 < ... < e_k into k cells, level i being the cell [e_i, e_i+1)
+// represented by its midpoint. Values outside are clamped to the end cells.
+#[derive(Debug, Clone)]
+pub struct BreakpointQuantizer<T: FiniteFloat + PartialOrd, const DIM: usize> {
+    edges: [Vec<T>; DIM],
+}
+
+impl<T: FiniteFloat + PartialOrd, const DIM: usize> BreakpointQuantizer<T, DIM> {
+    pub fn with_edges(edges: [Vec<T>; DIM]) -> Self {
+        for axis in edges.iter() {
+            assert!(axis.len() >= 2, "each axis needs at least two edges");
+            assert!(
+                axis.windows(2).all(|w| w[0] < w[1]),
+                "edges must be strictly increasing",
+            );
+        }
+        BreakpointQuantizer { edges }
+    }
+
+    pub fn edges(&self) -> &[Vec<T>; DIM] {
+        &self.edges
+    }
+
+    fn cell(&self, i: usize, x: T) -> usize {
+        let axis = &self.edges[i];
+        axis.partition_point(|e| *e <= x)
+            .saturating_sub(1)
+            .min(axis.len() - 2)
+    }
+}
+
+impl<T: FiniteFloat + PartialOrd, const DIM: usize> HasQuantizationMethods<T, DIM> for BreakpointQuantizer<T, DIM> {
+    // There is no rounding to choose, both look up the containing cell
+    fn quantize_ieee754(&self, x: [T; DIM]) -> [usize; DIM] {
+        self.quantize(x)
+    }
+
+    fn quantize(&self, x: [T; DIM]) -> [usize; DIM] {
+        from_fn(|i| self.cell(i, x[i]))
+    }
+
+    // Levels past the last cell are clamped to it
+    fn dequantize(&self, n: [usize; DIM]) -> [T; DIM] {
+        from_fn(|i| {
+            let axis = &self.edges[i];
+            let k = n[i].min(axis.len() - 2);
+            (axis[k] + axis[k + 1]) / T::from_usize(2)
+        })
+    }
+
+    fn n_levels(&self) -> [usize; DIM] {
+        from_fn(|i| self.edges[i].len() - 1)
+    }
+
+    // Cell index plus the relative position inside the cell, centered so that
+    // a midpoint maps to its level exactly
+    fn cell_coordinate(&self, x: [T; DIM]) -> [T; DIM] {
+        from_fn(|i| {
+            let axis = &self.edges[i];
+            let n = self.cell(i, x[i]);
+            let t = (x[i] - axis[n]) / (axis[n + 1] - axis[n]);
+            T::from_usize(n) + t - T::from_usize(1) / T::from_usize(2)
+        })
+    }
+}
@@ -0,0 +1,111 @@
+use crate::traits::{HasQuantizationMethods, TranscendentalFloat};
+use crate::utils::quantizer::Quantizer;
+use std::array::from_fn;
+
+// Companding quantizers: `x` is compressed into `u`, `u` is quantized
+// linearly and the levels are expanded back. Levels are dense near the
+// origin and sparse further away. Values below the origin clamp to it.
+
+// Distance above the origin, clamped at zero
+fn above<T: TranscendentalFloat>(x: T, origin: T) -> T {
+    if x > origin { x - origin } else { T::from_usize(0) }
+}
+
+// Geometric level spacing, u = ln(1 + (x - origin) / scale).
+// `scale` sets the finest spacing: the smaller, the more logarithmic.
+#[derive(Debug, Clone, Copy)]
+pub struct LogQuantizer<T: TranscendentalFloat, const DIM: usize> {
+    origin: [T; DIM],
+    scale: [T; DIM],
+    companded: Quantizer<T, DIM>,
+}
+
+impl<T: TranscendentalFloat, const DIM: usize> LogQuantizer<T, DIM> {
+    pub fn with_n(origin: [T; DIM], b: [T; DIM], scale: [T; DIM], n_levels: [usize; DIM]) -> Self {
+        let one = T::from_usize(1);
+        let u_max = from_fn(|i| (one + above(b[i], origin[i]) / scale[i]).ln());
+        let companded = Quantizer::with_n([T::from_usize(0); DIM], u_max, n_levels);
+        LogQuantizer { origin, scale, companded }
+    }
+
+    fn compress(&self, x: [T; DIM]) -> [T; DIM] {
+        let one = T::from_usize(1);
+        from_fn(|i| (one + above(x[i], self.origin[i]) / self.scale[i]).ln())
+    }
+
+    fn expand(&self, u: [T; DIM]) -> [T; DIM] {
+        let one = T::from_usize(1);
+        from_fn(|i| self.origin[i] + self.scale[i] * (u[i].exp() - one))
+    }
+}
+
+impl<T: TranscendentalFloat, const DIM: usize> HasQuantizationMethods<T, DIM> for LogQuantizer<T, DIM> {
+    fn quantize_ieee754(&self, x: [T; DIM]) -> [usize; DIM] {
+        self.companded.quantize_ieee754(self.compress(x))
+    }
+
+    fn quantize(&self, x: [T; DIM]) -> [usize; DIM] {
+        self.companded.quantize(self.compress(x))
+    }
+
+    fn dequantize(&self, n: [usize; DIM]) -> [T; DIM] {
+        self.expand(self.companded.dequantize(n))
+    }
+
+    fn n_levels(&self) -> [usize; DIM] {
+        self.companded.n_levels
+    }
+
+    fn cell_coordinate(&self, x: [T; DIM]) -> [T; DIM] {
+        self.companded.cell_coordinate(self.compress(x))
+    }
+}
+
+// Power-law level spacing, level i at origin + (b - origin) * (i / (n - 1))^exponent.
+// An exponent above 1 packs levels towards the origin.
+#[derive(Debug, Clone, Copy)]
+pub struct PowerQuantizer<T: TranscendentalFloat, const DIM: usize> {
+    origin: [T; DIM],
+    span: [T; DIM],
+    exponent: T,
+    companded: Quantizer<T, DIM>,
+}
+
+impl<T: TranscendentalFloat, const DIM: usize> PowerQuantizer<T, DIM> {
+    pub fn with_n(origin: [T; DIM], b: [T; DIM], exponent: T, n_levels: [usize; DIM]) -> Self {
+        let span = from_fn(|i| b[i] - origin[i]);
+        let companded = Quantizer::with_n([T::from_usize(0); DIM], [T::from_usize(1); DIM], n_levels);
+        PowerQuantizer { origin, span, exponent, companded }
+    }
+
+    fn compress(&self, x: [T; DIM]) -> [T; DIM] {
+        let inverse = T::from_usize(1) / self.exponent;
+        from_fn(|i| (above(x[i], self.origin[i]) / self.span[i]).powf(inverse))
+    }
+
+    fn expand(&self, u: [T; DIM]) -> [T; DIM] {
+        from_fn(|i| self.origin[i] + self.span[i] * u[i].powf(self.exponent))
+    }
+}
+
+impl<T: TranscendentalFloat, const DIM: usize> HasQuantizationMethods<T, DIM> for PowerQuantizer<T, DIM> {
+    fn quantize_ieee754(&self, x: [T; DIM]) -> [usize; DIM] {
+        self.companded.quantize_ieee754(self.compress(x))
+    }
+
+    fn quantize(&self, x: [T; DIM]) -> [usize; DIM] {
+        self.companded.quantize(self.compress(x))
+    }
+
+    fn dequantize(&self, n: [usize; DIM]) -> [T; DIM] {
+        self.expand(self.companded.dequantize(n))
+    }
+
+    fn n_levels(&self) -> [usize; DIM] {
+        self.companded.n_levels
+    }
+
+    fn cell_coordinate(&self, x: [T; DIM]) -> [T; DIM] {
+        self.companded.cell_coordinate(self.compress(x))
+    }
+}
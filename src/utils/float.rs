@@ -0,0 +1,20 @@
+use crate::traits::{FiniteFloat, TranscendentalFloat};
+
+// Helpers on top of the float traits shared by the quantizers and planners
+
+// |x|, FiniteFloat has no sign of its own
+pub(crate) fn abs<T: FiniteFloat + PartialOrd>(x: T) -> T {
+    let zero = T::from_usize(0);
+    if x < zero { zero - x } else { x }
+}
+
+// Angle in [0, 2π), any number of turns away
+pub(crate) fn wrap_angle<T: TranscendentalFloat>(theta: T) -> T {
+    let two_pi = T::from_usize(2) * T::pi();
+    theta - (theta / two_pi).floor() * two_pi
+}
+
+// Level on a periodic axis, rounding may land on level n which is level 0
+pub(crate) fn wrap_level(n: usize, n_levels: usize) -> usize {
+    n % n_levels.max(1)
+}
@@ -0,0 +1,168 @@
+use crate::traits::HasQuantizationMethods;
+
+// Dense storage over the quantized grid. The first axis varies fastest.
+
+// Total number of cells of a grid with `n_levels`
+pub fn n_cells<const DIM: usize>(n_levels: [usize; DIM]) -> usize {
+    n_levels.iter().product()
+}
+
+// Position of the cell `n` in a dense buffer, None when it is off the grid
+pub fn flat_index<const DIM: usize>(n: [usize; DIM], n_levels: [usize; DIM]) -> Option<usize> {
+    let mut index = 0;
+    let mut stride = 1;
+    for i in 0..DIM {
+        if n[i] >= n_levels[i] {
+            return None;
+        }
+        index += n[i] * stride;
+        stride *= n_levels[i];
+    }
+    Some(index)
+}
+
+// Inverse of `flat_index`
+pub fn unflat_index<const DIM: usize>(mut index: usize, n_levels: [usize; DIM]) -> [usize; DIM] {
+    let mut n = [0; DIM];
+    for i in 0..DIM {
+        n[i] = index % n_levels[i];
+        index /= n_levels[i];
+    }
+    n
+}
+
+// Every cell of the box spanned by `lo` and `hi` (inclusive, either order),
+// clipped to the grid
+pub fn box_cells<const DIM: usize>(
+    lo: [usize; DIM],
+    hi: [usize; DIM],
+    n_levels: [usize; DIM],
+) -> Vec<[usize; DIM]> {
+    wrapping_box_cells(lo, hi, n_levels, [false; DIM])
+}
+
+// Same as `box_cells`, but along the periodic axes the box runs upwards from
+// `lo` to `hi`, so `lo > hi` is a box across the seam
+pub fn wrapping_box_cells<const DIM: usize>(
+    lo: [usize; DIM],
+    hi: [usize; DIM],
+    n_levels: [usize; DIM],
+    periodic: [bool; DIM],
+) -> Vec<[usize; DIM]> {
+    let mut min = [0; DIM];
+    let mut extent = [0; DIM];
+    for i in 0..DIM {
+        if n_levels[i] == 0 {
+            return vec![];
+        }
+        if periodic[i] {
+            min[i] = lo[i] % n_levels[i];
+            extent[i] = (hi[i] % n_levels[i] + n_levels[i] - min[i]) % n_levels[i] + 1;
+            continue;
+        }
+        let last = lo[i].max(hi[i]).min(n_levels[i] - 1);
+        min[i] = lo[i].min(hi[i]);
+        if min[i] > last {
+            return vec![];
+        }
+        extent[i] = last - min[i] + 1;
+    }
+    (0..n_cells(extent))
+        .map(|index| {
+            let offset = unflat_index(index, extent);
+            let mut n = min;
+            for i in 0..DIM {
+                n[i] = (n[i] + offset[i]) % n_levels[i];
+            }
+            n
+        })
+        .collect()
+}
+
+// Offsets to the 2 * DIM neighbors sharing a face
+pub fn face_offsets<const DIM: usize>() -> Vec<[isize; DIM]> {
+    let mut offsets = Vec::with_capacity(2 * DIM);
+    for i in 0..DIM {
+        for d in [1, -1] {
+            let mut offset = [0; DIM];
+            offset[i] = d;
+            offsets.push(offset);
+        }
+    }
+    offsets
+}
+
+// Offsets in {-1, 0, 1}^DIM changing between 1 and `max_axes` coordinates,
+// ex. DIM=3: 1 gives the 6, 2 the 18 and 3 the 26-neighborhood
+pub fn neighborhood_offsets<const DIM: usize>(max_axes: usize) -> Vec<[isize; DIM]> {
+    let mut offsets = vec![];
+    for k in 0..3usize.pow(DIM as u32) {
+        let mut rest = k;
+        let mut offset = [0; DIM];
+        for o in offset.iter_mut() {
+            *o = (rest % 3) as isize - 1;
+            rest /= 3;
+        }
+        let changed = offset.iter().filter(|&&o| o != 0).count();
+        if changed >= 1 && changed <= max_axes {
+            offsets.push(offset);
+        }
+    }
+    offsets
+}
+
+// Cell at `n + offset`, None when it leaves the grid
+pub fn offset_cell<const DIM: usize>(
+    n: [usize; DIM],
+    offset: [isize; DIM],
+    n_levels: [usize; DIM],
+) -> Option<[usize; DIM]> {
+    wrapping_offset_cell(n, offset, n_levels, [false; DIM])
+}
+
+// Cell at `n + offset` wrapping around the periodic axes,
+// None when it leaves the grid along a bounded axis
+pub fn wrapping_offset_cell<const DIM: usize>(
+    n: [usize; DIM],
+    offset: [isize; DIM],
+    n_levels: [usize; DIM],
+    periodic: [bool; DIM],
+) -> Option<[usize; DIM]> {
+    let mut m = [0; DIM];
+    for i in 0..DIM {
+        if periodic[i] && n_levels[i] > 0 {
+            let len = n_levels[i] as isize;
+            m[i] = (n[i] as isize + offset[i]).rem_euclid(len) as usize;
+            continue;
+        }
+        let v = n[i].checked_add_signed(offset[i])?;
+        if v >= n_levels[i] {
+            return None;
+        }
+        m[i] = v;
+    }
+    Some(m)
+}
+
+// Distinct neighbors of `n` through `offsets` on the grid of the quantizer
+pub fn grid_neighbors<T, Q, const DIM: usize>(
+    quantizer: &Q,
+    n: [usize; DIM],
+    offsets: &[[isize; DIM]],
+) -> Vec<[usize; DIM]>
+where
+    Q: HasQuantizationMethods<T, DIM>,
+{
+    let n_levels = quantizer.n_levels();
+    let periodic = quantizer.periodic();
+    let mut neighbors = Vec::with_capacity(offsets.len());
+    for &offset in offsets {
+        if let Some(m) = wrapping_offset_cell(n, offset, n_levels, periodic)
+            && m != n
+            && !neighbors.contains(&m)
+        {
+            neighbors.push(m);
+        }
+    }
+    neighbors
+}
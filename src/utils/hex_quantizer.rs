@@ -0,0 +1,104 @@
+use crate::traits::{HasQuantizationMethods, TranscendentalFloat};
+use crate::utils::float::abs;
+use crate::utils::grid::grid_neighbors;
+
+// Axial directions to the six neighbors of a hex, all at the same distance
+pub const HEX_OFFSETS: [[isize; 2]; 6] = [
+    [1, 0], [1, -1], [0, -1],
+    [-1, 0], [-1, 1], [0, 1],
+];
+
+// Pointy-top hexagonal quantizer. Levels are axial coordinates [q, r] over a
+// rhombus of n_levels[0] x n_levels[1] hexes whose hex [0, 0] is centered at
+// `origin`. `size` is the distance from a center to a corner.
+#[derive(Debug, Clone, Copy)]
+pub struct HexQuantizer<T: TranscendentalFloat> {
+    origin: [T; 2],
+    size: T,
+    n_levels: [usize; 2],
+}
+
+impl<T: TranscendentalFloat> HexQuantizer<T> {
+    pub fn with_n(origin: [T; 2], size: T, n_levels: [usize; 2]) -> Self {
+        HexQuantizer { origin, size, n_levels }
+    }
+
+    pub fn size(&self) -> T {
+        self.size
+    }
+
+    // Distance between hex centers
+    pub fn spacing(&self) -> T {
+        T::from_usize(3).sqrt() * self.size
+    }
+
+    // Nearest hex in cube coordinates, clamped to the map
+    fn cube_round(&self, [q, r]: [T; 2]) -> [usize; 2] {
+        let zero = T::from_usize(0);
+        let s = zero - q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = (abs(rq - q), abs(rr - r), abs(rs - s));
+        // The coordinate that moved the most is rebuilt from the other two
+        if dq > dr && dq > ds {
+            rq = zero - rr - rs;
+        } else if dr > ds {
+            rr = zero - rq - rs;
+        }
+        let clamp = |v: T, n: usize| {
+            if v < zero { 0 } else { v.to_usize().min(n.saturating_sub(1)) }
+        };
+        [clamp(rq, self.n_levels[0]), clamp(rr, self.n_levels[1])]
+    }
+}
+
+impl<T: TranscendentalFloat> HasQuantizationMethods<T, 2> for HexQuantizer<T> {
+    // Truncation has no meaning on a hex lattice, both use cube rounding
+    fn quantize_ieee754(&self, x: [T; 2]) -> [usize; 2] {
+        self.quantize(x)
+    }
+
+    fn quantize(&self, x: [T; 2]) -> [usize; 2] {
+        self.cube_round(self.cell_coordinate(x))
+    }
+
+    fn dequantize(&self, n: [usize; 2]) -> [T; 2] {
+        let sqrt3 = T::from_usize(3).sqrt();
+        let two = T::from_usize(2);
+        let q = T::from_usize(n[0]);
+        let r = T::from_usize(n[1]);
+        [
+            self.origin[0] + self.size * sqrt3 * (q + r / two),
+            self.origin[1] + self.size * T::from_usize(3) / two * r,
+        ]
+    }
+
+    fn n_levels(&self) -> [usize; 2] {
+        self.n_levels
+    }
+
+    // Fractional axial coordinate [q, r]
+    fn cell_coordinate(&self, x: [T; 2]) -> [T; 2] {
+        let sqrt3 = T::from_usize(3).sqrt();
+        let three = T::from_usize(3);
+        let dx = (x[0] - self.origin[0]) / self.size;
+        let dy = (x[1] - self.origin[1]) / self.size;
+        [
+            sqrt3 / three * dx - dy / three,
+            T::from_usize(2) / three * dy,
+        ]
+    }
+}
+
+// The up to six neighbors of a hex on the map
+pub fn hex_neighbors<T: TranscendentalFloat>(quantizer: &HexQuantizer<T>, n: [usize; 2]) -> Vec<[usize; 2]> {
+    grid_neighbors(quantizer, n, &HEX_OFFSETS)
+}
+
+// Number of hex steps between two hexes, an exact heuristic on an open map
+pub fn hex_distance(a: [usize; 2], b: [usize; 2]) -> usize {
+    let [aq, ar] = a.map(|v| v as isize);
+    let [bq, br] = b.map(|v| v as isize);
+    let dq = aq - bq;
+    let dr = ar - br;
+    ((dq.abs() + dr.abs() + (dq + dr).abs()) / 2) as usize
+}
@@ -0,0 +1,117 @@
+use std::array::from_fn;
+
+use crate::traits::FiniteFloat;
+
+// Lower and upper corner with the blend weight along one axis, clamped to
+// the grid, or wrapped so that the last level blends into level 0
+fn axis_corner<T: FiniteFloat + PartialOrd>(c: T, n_levels: usize, periodic: bool) -> (usize, usize, T) {
+    let zero = T::from_usize(0);
+    if periodic && n_levels > 0 {
+        let n = T::from_usize(n_levels);
+        let c = c - (c / n).floor() * n;
+        let i = c.floor().to_usize().min(n_levels - 1);
+        return (i, (i + 1) % n_levels, c - T::from_usize(i));
+    }
+    if n_levels < 2 || c <= zero {
+        return (0, 0, zero);
+    }
+    let last = T::from_usize(n_levels - 1);
+    if c >= last {
+        return (n_levels - 2, n_levels - 1, T::from_usize(1));
+    }
+    let i = c.to_usize().min(n_levels - 2);
+    (i, i + 1, c - T::from_usize(i))
+}
+
+// Multilinear blend of the 2^DIM cells around a continuous cell coordinate.
+// Missing cells (None) are left out and the remaining weights renormalized,
+// so values next to walls or unreachable areas stay meaningful.
+pub fn interpolate_vector<T, const DIM: usize, const K: usize, F>(
+    coordinate: [T; DIM],
+    n_levels: [usize; DIM],
+    sample: F,
+) -> Option<[T; K]>
+where
+    T: FiniteFloat + PartialOrd,
+    F: FnMut([usize; DIM]) -> Option<[T; K]>,
+{
+    wrapping_interpolate_vector(coordinate, n_levels, [false; DIM], sample)
+}
+
+// Same as `interpolate_vector`, blending across the seam of periodic axes
+pub fn wrapping_interpolate_vector<T, const DIM: usize, const K: usize, F>(
+    coordinate: [T; DIM],
+    n_levels: [usize; DIM],
+    periodic: [bool; DIM],
+    mut sample: F,
+) -> Option<[T; K]>
+where
+    T: FiniteFloat + PartialOrd,
+    F: FnMut([usize; DIM]) -> Option<[T; K]>,
+{
+    let zero = T::from_usize(0);
+    let one = T::from_usize(1);
+    let corners: [(usize, usize, T); DIM] = from_fn(|i| {
+        axis_corner(coordinate[i], n_levels[i], periodic[i])
+    });
+
+    let mut sum = [zero; K];
+    let mut total_weight = zero;
+    for bits in 0..(1usize << DIM) {
+        let mut n = [0; DIM];
+        let mut weight = one;
+        for i in 0..DIM {
+            let (lower, upper, t) = corners[i];
+            if bits >> i & 1 == 1 {
+                n[i] = upper;
+                weight = weight * t;
+            } else {
+                n[i] = lower;
+                weight = weight * (one - t);
+            }
+        }
+        if weight <= zero {
+            continue;
+        }
+        if let Some(value) = sample(n) {
+            for k in 0..K {
+                sum[k] = sum[k] + value[k] * weight;
+            }
+            total_weight = total_weight + weight;
+        }
+    }
+
+    if total_weight > zero {
+        Some(from_fn(|k| sum[k] / total_weight))
+    } else {
+        None
+    }
+}
+
+// Scalar version of `interpolate_vector`
+pub fn interpolate<T, const DIM: usize, F>(
+    coordinate: [T; DIM],
+    n_levels: [usize; DIM],
+    mut sample: F,
+) -> Option<T>
+where
+    T: FiniteFloat + PartialOrd,
+    F: FnMut([usize; DIM]) -> Option<T>,
+{
+    interpolate_vector(coordinate, n_levels, |n| sample(n).map(|v| [v])).map(|[v]| v)
+}
+
+// Scalar version of `wrapping_interpolate_vector`
+pub fn wrapping_interpolate<T, const DIM: usize, F>(
+    coordinate: [T; DIM],
+    n_levels: [usize; DIM],
+    periodic: [bool; DIM],
+    mut sample: F,
+) -> Option<T>
+where
+    T: FiniteFloat + PartialOrd,
+    F: FnMut([usize; DIM]) -> Option<T>,
+{
+    wrapping_interpolate_vector(coordinate, n_levels, periodic, |n| sample(n).map(|v| [v]))
+        .map(|[v]| v)
+}
@@ -1,3 +1,16 @@
+pub mod adaptive_tree;
+pub mod affine_quantizer;
+pub mod breakpoint_quantizer;
+pub mod companding_quantizer;
+pub(crate) mod float;
+pub mod grid;
+pub mod hex_quantizer;
+pub mod interpolation;
+pub mod polar_quantizer;
+pub mod product_quantizer;
 pub mod quantizer;
+pub mod reservation_table;
+pub mod terrain;
+pub mod voxel;
 //pub mod quantizer_2d;
 //pub mod quantizer_3d;
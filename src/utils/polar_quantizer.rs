@@ -0,0 +1,165 @@
+use crate::traits::{HasQuantizationMethods, TranscendentalFloat};
+use crate::utils::float::{wrap_angle, wrap_level};
+
+// Angular quantizers. Input and output stay Cartesian, the levels are
+// [radius, angle...] around `center`. Azimuth wraps around at 2π, so the
+// last azimuth level is next to the first one.
+
+// Clamp a level to the grid, radius and elevation are not periodic
+fn clamp_level(n: usize, n_levels: usize) -> usize {
+    n.min(n_levels.saturating_sub(1))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PolarQuantizer<T: TranscendentalFloat> {
+    center: [T; 2],
+    n_levels: [usize; 2],
+    radius_step: T,
+    angle_step: T,
+}
+
+impl<T: TranscendentalFloat> PolarQuantizer<T> {
+    // `n_levels` is [radius levels over [0, max_radius], angle levels over 2π]
+    pub fn with_n(center: [T; 2], max_radius: T, n_levels: [usize; 2]) -> Self {
+        let radius_step = max_radius / T::from_usize(n_levels[0].saturating_sub(1));
+        let angle_step = T::from_usize(2) * T::pi() / T::from_usize(n_levels[1]);
+        PolarQuantizer { center, n_levels, radius_step, angle_step }
+    }
+
+    // Cartesian to [radius, angle in [0, 2π)]
+    pub fn to_polar(&self, x: [T; 2]) -> [T; 2] {
+        let dx = x[0] - self.center[0];
+        let dy = x[1] - self.center[1];
+        [(dx * dx + dy * dy).sqrt(), wrap_angle(dy.atan2(dx))]
+    }
+
+    pub fn from_polar(&self, [r, theta]: [T; 2]) -> [T; 2] {
+        [self.center[0] + r * theta.cos(), self.center[1] + r * theta.sin()]
+    }
+}
+
+impl<T: TranscendentalFloat> HasQuantizationMethods<T, 2> for PolarQuantizer<T> {
+    fn quantize_ieee754(&self, x: [T; 2]) -> [usize; 2] {
+        let [cr, ct] = self.cell_coordinate(x);
+        [
+            clamp_level(cr.round_ieee754(), self.n_levels[0]),
+            wrap_level(ct.round_ieee754(), self.n_levels[1]),
+        ]
+    }
+
+    fn quantize(&self, x: [T; 2]) -> [usize; 2] {
+        let [cr, ct] = self.cell_coordinate(x);
+        [
+            clamp_level(cr.round().to_usize(), self.n_levels[0]),
+            wrap_level(ct.round().to_usize(), self.n_levels[1]),
+        ]
+    }
+
+    fn dequantize(&self, n: [usize; 2]) -> [T; 2] {
+        self.from_polar([
+            T::from_usize(n[0]) * self.radius_step,
+            T::from_usize(n[1]) * self.angle_step,
+        ])
+    }
+
+    fn n_levels(&self) -> [usize; 2] {
+        self.n_levels
+    }
+
+    fn periodic(&self) -> [bool; 2] {
+        [false, true]
+    }
+
+    fn cell_coordinate(&self, x: [T; 2]) -> [T; 2] {
+        let [r, theta] = self.to_polar(x);
+        [r / self.radius_step, theta / self.angle_step]
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SphericalQuantizer<T: TranscendentalFloat> {
+    center: [T; 3],
+    n_levels: [usize; 3],
+    radius_step: T,
+    azimuth_step: T,
+    elevation_step: T,
+}
+
+impl<T: TranscendentalFloat> SphericalQuantizer<T> {
+    // `n_levels` is [radius levels over [0, max_radius], azimuth levels over 2π,
+    // elevation levels over [-π/2, π/2] with both poles included]
+    pub fn with_n(center: [T; 3], max_radius: T, n_levels: [usize; 3]) -> Self {
+        let radius_step = max_radius / T::from_usize(n_levels[0].saturating_sub(1));
+        let azimuth_step = T::from_usize(2) * T::pi() / T::from_usize(n_levels[1]);
+        let elevation_step = T::pi() / T::from_usize(n_levels[2].saturating_sub(1));
+        SphericalQuantizer { center, n_levels, radius_step, azimuth_step, elevation_step }
+    }
+
+    // Cartesian to [radius, azimuth in [0, 2π), elevation in [-π/2, π/2]]
+    pub fn to_spherical(&self, x: [T; 3]) -> [T; 3] {
+        let zero = T::from_usize(0);
+        let dx = x[0] - self.center[0];
+        let dy = x[1] - self.center[1];
+        let dz = x[2] - self.center[2];
+        let r = (dx * dx + dy * dy + dz * dz).sqrt();
+        let elevation = if r > zero { (dz / r).asin() } else { zero };
+        [r, wrap_angle(dy.atan2(dx)), elevation]
+    }
+
+    pub fn from_spherical(&self, [r, azimuth, elevation]: [T; 3]) -> [T; 3] {
+        let horizontal = r * elevation.cos();
+        [
+            self.center[0] + horizontal * azimuth.cos(),
+            self.center[1] + horizontal * azimuth.sin(),
+            self.center[2] + r * elevation.sin(),
+        ]
+    }
+}
+
+impl<T: TranscendentalFloat> HasQuantizationMethods<T, 3> for SphericalQuantizer<T> {
+    fn quantize_ieee754(&self, x: [T; 3]) -> [usize; 3] {
+        let [cr, ca, ce] = self.cell_coordinate(x);
+        [
+            clamp_level(cr.round_ieee754(), self.n_levels[0]),
+            wrap_level(ca.round_ieee754(), self.n_levels[1]),
+            clamp_level(ce.round_ieee754(), self.n_levels[2]),
+        ]
+    }
+
+    fn quantize(&self, x: [T; 3]) -> [usize; 3] {
+        let [cr, ca, ce] = self.cell_coordinate(x);
+        [
+            clamp_level(cr.round().to_usize(), self.n_levels[0]),
+            wrap_level(ca.round().to_usize(), self.n_levels[1]),
+            clamp_level(ce.round().to_usize(), self.n_levels[2]),
+        ]
+    }
+
+    fn dequantize(&self, n: [usize; 3]) -> [T; 3] {
+        let half_pi = T::pi() / T::from_usize(2);
+        self.from_spherical([
+            T::from_usize(n[0]) * self.radius_step,
+            T::from_usize(n[1]) * self.azimuth_step,
+            T::from_usize(n[2]) * self.elevation_step - half_pi,
+        ])
+    }
+
+    fn n_levels(&self) -> [usize; 3] {
+        self.n_levels
+    }
+
+    fn periodic(&self) -> [bool; 3] {
+        [false, true, false]
+    }
+
+    // Elevation is counted from the south pole
+    fn cell_coordinate(&self, x: [T; 3]) -> [T; 3] {
+        let half_pi = T::pi() / T::from_usize(2);
+        let [r, azimuth, elevation] = self.to_spherical(x);
+        [
+            r / self.radius_step,
+            azimuth / self.azimuth_step,
+            (elevation + half_pi) / self.elevation_step,
+        ]
+    }
+}
@@ -0,0 +1,71 @@
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use std::array::from_fn;
+use std::fmt::Debug;
+
+// 1-D quantizer usable as an axis of `ProductQuantizer`, any quantizer that
+// is also Debug and Clone
+pub trait AxisQuantizer<T>: HasQuantizationMethods<T, 1> + Debug {
+    fn clone_box(&self) -> Box<dyn AxisQuantizer<T>>;
+}
+
+impl<T, Q> AxisQuantizer<T> for Q
+where
+    Q: HasQuantizationMethods<T, 1> + Debug + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn AxisQuantizer<T>> {
+        Box::new(self.clone())
+    }
+}
+
+impl<T> Clone for Box<dyn AxisQuantizer<T>> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+// N-D quantizer made of independent 1-D quantizers, one per axis, so that
+// different kinds can be mixed, ex. linear x and y with a periodic heading.
+#[derive(Debug, Clone)]
+pub struct ProductQuantizer<T: FiniteFloat, const DIM: usize> {
+    axes: [Box<dyn AxisQuantizer<T>>; DIM],
+}
+
+impl<T: FiniteFloat, const DIM: usize> ProductQuantizer<T, DIM> {
+    pub fn new(axes: [Box<dyn AxisQuantizer<T>>; DIM]) -> Self {
+        ProductQuantizer { axes }
+    }
+
+    pub fn axis(&self, i: usize) -> &dyn AxisQuantizer<T> {
+        self.axes[i].as_ref()
+    }
+}
+
+impl<T: FiniteFloat, const DIM: usize> HasQuantizationMethods<T, DIM> for ProductQuantizer<T, DIM> {
+    fn quantize_ieee754(&self, x: [T; DIM]) -> [usize; DIM] {
+        from_fn(|i| self.axes[i].quantize_ieee754([x[i]])[0])
+    }
+
+    fn quantize(&self, x: [T; DIM]) -> [usize; DIM] {
+        from_fn(|i| self.axes[i].quantize([x[i]])[0])
+    }
+
+    fn dequantize(&self, n: [usize; DIM]) -> [T; DIM] {
+        from_fn(|i| self.axes[i].dequantize([n[i]])[0])
+    }
+
+    fn n_levels(&self) -> [usize; DIM] {
+        from_fn(|i| self.axes[i].n_levels()[0])
+    }
+
+    fn cell_coordinate(&self, x: [T; DIM]) -> [T; DIM] {
+        from_fn(|i| self.axes[i].cell_coordinate([x[i]])[0])
+    }
+
+    fn periodic(&self) -> [bool; DIM] {
+        from_fn(|i| self.axes[i].periodic()[0])
+    }
+
+    fn wrapped_difference(&self, from: [T; DIM], to: [T; DIM]) -> [T; DIM] {
+        from_fn(|i| self.axes[i].wrapped_difference([from[i]], [to[i]])[0])
+    }
+}
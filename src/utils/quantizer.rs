@@ -1,47 +1,188 @@
 use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::float::wrap_level;
 use std::array::from_fn;
 
+// Where the levels sit within [a, b]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    // Level 0 at `a` and the last level at `b`, ex. N=3: a, (a+b)/2, b
+    #[default]
+    Vertex,
+    // N cells spanning [a, b), each level at the center of its cell
+    CellCentered,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct Quantizer<T: FiniteFloat, const DIM: usize> {
     a: [T; DIM],            // Lower bounds
     b: [T; DIM],            // Upper bounds
     pub n_levels: [usize; DIM], // ex. DIM=1,N=4: 0,1,2,3
     step_size: [T; DIM],
+    origin: [T; DIM],       // Position of level 0
+    alignment: Alignment,
+    periodic: [bool; DIM],  // Wrap-around axes
+    fixed_step: bool,       // Built from a step size, which periodic axes keep
 }
 
 // All intializations
 impl<T: FiniteFloat, const DIM: usize> Quantizer<T, DIM> {
     pub fn with_n(a: [T; DIM], b: [T; DIM], n_levels: [usize; DIM]) -> Self {
+        Self::with_n_aligned(a, b, n_levels, Alignment::Vertex)
+    }
+
+    pub fn with_step_size(a: [T; DIM], b: [T; DIM], step_size: [T; DIM]) -> Self {
+        Self::with_step_size_aligned(a, b, step_size, Alignment::Vertex)
+    }
+
+    pub fn with_n_aligned(
+        a: [T; DIM],
+        b: [T; DIM],
+        n_levels: [usize; DIM],
+        alignment: Alignment,
+    ) -> Self {
         let step_size = from_fn(|i| {
-            (b[i] - a[i]) / T::from_usize(n_levels[i].saturating_sub(1))
+            let n_steps = match alignment {
+                Alignment::Vertex => n_levels[i].saturating_sub(1),
+                Alignment::CellCentered => n_levels[i],
+            };
+            (b[i] - a[i]) / T::from_usize(n_steps)
         });
-        Quantizer { a, b, n_levels, step_size }
+        let origin = Self::origin(a, step_size, alignment);
+        Quantizer {
+            a, b, n_levels, step_size, origin, alignment,
+            periodic: [false; DIM],
+            fixed_step: false,
+        }
     }
 
-    pub fn with_step_size(a: [T; DIM], b: [T; DIM], step_size: [T; DIM]) -> Self {
+    pub fn with_step_size_aligned(
+        a: [T; DIM],
+        b: [T; DIM],
+        step_size: [T; DIM],
+        alignment: Alignment,
+    ) -> Self {
         let n_levels = from_fn(|i| {
-            ((b[i] - a[i]) / step_size[i] + T::from_usize(1)).to_usize()
+            let n_steps = (b[i] - a[i]) / step_size[i];
+            match alignment {
+                Alignment::Vertex => (n_steps + T::from_usize(1)).to_usize(),
+                Alignment::CellCentered => n_steps.to_usize(),
+            }
         });
-        Quantizer { a, b, n_levels, step_size }
+        let origin = Self::origin(a, step_size, alignment);
+        Quantizer {
+            a, b, n_levels, step_size, origin, alignment,
+            periodic: [false; DIM],
+            fixed_step: true,
+        }
+    }
+
+    // Wrap the selected axes around. Since b coincides with a on such an axis,
+    // its levels are spread over [a, b): n levels with a step of (b - a) / n,
+    // or (b - a) / step levels when the quantizer was built from a step size.
+    pub fn with_periodic(mut self, periodic: [bool; DIM]) -> Self {
+        for (i, _) in periodic.iter().enumerate().filter(|(_, p)| **p) {
+            let span = self.b[i] - self.a[i];
+            if self.fixed_step {
+                self.n_levels[i] = (span / self.step_size[i]).round().to_usize();
+            } else {
+                self.step_size[i] = span / T::from_usize(self.n_levels[i]);
+            }
+        }
+        self.origin = Self::origin(self.a, self.step_size, self.alignment);
+        self.periodic = periodic;
+        self
+    }
+
+    fn origin(a: [T; DIM], step_size: [T; DIM], alignment: Alignment) -> [T; DIM] {
+        match alignment {
+            Alignment::Vertex => a,
+            Alignment::CellCentered => from_fn(|i| {
+                a[i] + step_size[i] / T::from_usize(2)
+            }),
+        }
+    }
+}
+
+// Boundaries and resolution
+impl<T: FiniteFloat, const DIM: usize> Quantizer<T, DIM> {
+    pub fn lower_bounds(&self) -> [T; DIM] {
+        self.a
+    }
+
+    pub fn step_size(&self) -> [T; DIM] {
+        self.step_size
+    }
+
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+}
+
+impl<T: FiniteFloat, const DIM: usize> Quantizer<T, DIM> {
+    // Bring `x` back into [a, b) along the periodic axes
+    fn wrap(&self, x: [T; DIM]) -> [T; DIM] {
+        from_fn(|i| {
+            if self.periodic[i] {
+                let span = self.b[i] - self.a[i];
+                x[i] - ((x[i] - self.a[i]) / span).floor() * span
+            } else {
+                x[i]
+            }
+        })
+    }
+
+    fn periodic_level(&self, i: usize, n: usize) -> usize {
+        if self.periodic[i] { wrap_level(n, self.n_levels[i]) } else { n }
     }
 }
 
 impl<T: FiniteFloat, const DIM: usize> HasQuantizationMethods<T, DIM> for Quantizer<T, DIM> {
+    // Truncates from `a`, which is the tile index when the levels are cell centered
     fn quantize_ieee754(&self, x: [T; DIM]) -> [usize; DIM] {
+        let x = self.wrap(x);
         from_fn(|i| {
-            ((x[i] - self.a[i]) / self.step_size[i]).round_ieee754()
+            self.periodic_level(i, ((x[i] - self.a[i]) / self.step_size[i]).round_ieee754())
         })
     }
 
     fn quantize(&self, x: [T; DIM]) -> [usize; DIM] {
+        let x = self.wrap(x);
         from_fn(|i| {
-            ((x[i] - self.a[i]) / self.step_size[i]).round().to_usize()
+            self.periodic_level(i, ((x[i] - self.origin[i]) / self.step_size[i]).round().to_usize())
         })
     }
 
     fn dequantize(&self, n: [usize; DIM]) -> [T; DIM] {
         from_fn(|i| {
-            self.a[i] + T::from_usize(n[i]) * self.step_size[i]
+            self.origin[i] + T::from_usize(self.periodic_level(i, n[i])) * self.step_size[i]
+        })
+    }
+
+    fn n_levels(&self) -> [usize; DIM] {
+        self.n_levels
+    }
+
+    fn cell_coordinate(&self, x: [T; DIM]) -> [T; DIM] {
+        let x = self.wrap(x);
+        from_fn(|i| {
+            (x[i] - self.origin[i]) / self.step_size[i]
+        })
+    }
+
+    fn periodic(&self) -> [bool; DIM] {
+        self.periodic
+    }
+
+    // Periodic axes reduce the difference to [-span/2, span/2]
+    fn wrapped_difference(&self, from: [T; DIM], to: [T; DIM]) -> [T; DIM] {
+        from_fn(|i| {
+            let d = to[i] - from[i];
+            if self.periodic[i] {
+                let span = self.b[i] - self.a[i];
+                d - (d / span).round() * span
+            } else {
+                d
+            }
         })
     }
 }
\ No newline at end of file
@@ -0,0 +1,104 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::traits::HasQuantizationMethods;
+
+// Cells claimed by agents per time step. Float positions go through a shared
+// quantizer, so agents whose coordinates differ slightly still agree on which
+// cell is taken. Moves are recorded too, to keep agents from swapping cells.
+#[derive(Debug, Clone, Default)]
+pub struct ReservationTable<const DIM: usize> {
+    cells: HashSet<([usize; DIM], usize)>,
+    moves: HashSet<([usize; DIM], [usize; DIM], usize)>,
+    parked: HashMap<[usize; DIM], usize>,   // Taken from that time step on
+}
+
+impl<const DIM: usize> ReservationTable<DIM> {
+    pub fn new() -> Self {
+        ReservationTable {
+            cells: HashSet::new(),
+            moves: HashSet::new(),
+            parked: HashMap::new(),
+        }
+    }
+
+    pub fn reserve(&mut self, n: [usize; DIM], t: usize) {
+        self.cells.insert((n, t));
+    }
+
+    // Reserve the cell containing `x` at time step `t`
+    pub fn reserve_position<T, Q>(&mut self, quantizer: &Q, x: [T; DIM], t: usize)
+    where
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        self.reserve(quantizer.quantize(x), t);
+    }
+
+    // Reserve the move from `from` into `to` arriving at `t`, with both cells
+    pub fn reserve_move(&mut self, from: [usize; DIM], to: [usize; DIM], t: usize) {
+        self.reserve(to, t);
+        if from != to {
+            self.moves.insert((from, to, t));
+        }
+    }
+
+    // Reserve `n` from time step `t` on, ex. for an agent resting at its goal
+    pub fn park(&mut self, n: [usize; DIM], t: usize) {
+        let since = self.parked.entry(n).or_insert(t);
+        *since = (*since).min(t);
+    }
+
+    pub fn park_position<T, Q>(&mut self, quantizer: &Q, x: [T; DIM], t: usize)
+    where
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        self.park(quantizer.quantize(x), t);
+    }
+
+    // Reserve consecutive (cell, time step) pairs and park at the last one
+    pub fn reserve_path(&mut self, path: &[([usize; DIM], usize)]) {
+        for (k, &(n, t)) in path.iter().enumerate() {
+            match k.checked_sub(1).map(|j| path[j].0) {
+                Some(from) => self.reserve_move(from, n, t),
+                None => self.reserve(n, t),
+            }
+        }
+        if let Some(&(n, t)) = path.last() {
+            self.park(n, t);
+        }
+    }
+
+    // Same as `reserve_path` for timed waypoints in world space
+    pub fn reserve_timed_path<T, Q>(&mut self, quantizer: &Q, path: &[(usize, [T; DIM])])
+    where
+        T: Copy,
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        let cells: Vec<_> = path.iter()
+            .map(|&(t, x)| (quantizer.quantize(x), t))
+            .collect();
+        self.reserve_path(&cells);
+    }
+
+    pub fn is_reserved(&self, n: [usize; DIM], t: usize) -> bool {
+        self.cells.contains(&(n, t)) || self.parked.get(&n).is_some_and(|&since| since <= t)
+    }
+
+    pub fn is_position_reserved<T, Q>(&self, quantizer: &Q, x: [T; DIM], t: usize) -> bool
+    where
+        Q: HasQuantizationMethods<T, DIM>,
+    {
+        self.is_reserved(quantizer.quantize(x), t)
+    }
+
+    // Whether moving from `from` into `to` arriving at `t` collides with a
+    // reservation, either in `to` or head-on with a reserved move
+    pub fn is_move_blocked(&self, from: [usize; DIM], to: [usize; DIM], t: usize) -> bool {
+        self.is_reserved(to, t) || self.moves.contains(&(to, from, t))
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.moves.clear();
+        self.parked.clear();
+    }
+}
@@ -0,0 +1,146 @@
+use crate::traits::{HasQuantizationMethods, TranscendentalFloat};
+use crate::utils::grid::{flat_index, n_cells, unflat_index, wrapping_offset_cell};
+use crate::utils::interpolation::wrapping_interpolate;
+use crate::utils::quantizer::Quantizer;
+
+// Elevation sampled at every level of a 2D quantizer. A step between two
+// cells costs its horizontal length plus weighted ascent and descent, and is
+// refused when steeper than `max_slope`. Costs are scaled by `cost_scale` and
+// rounded up to `u32`, 1000 per world unit by default like the voxel costs.
+#[derive(Debug, Clone)]
+pub struct Heightmap<T: TranscendentalFloat> {
+    quantizer: Quantizer<T, 2>,
+    heights: Vec<T>,
+    max_slope: Option<T>,   // Rise over run
+    climb_weight: T,        // Cost per unit of ascent
+    descent_weight: T,      // Cost per unit of descent
+    cost_scale: T,
+}
+
+impl<T: TranscendentalFloat> Heightmap<T> {
+    // `heights` in the order of `flat_index`
+    pub fn new(quantizer: Quantizer<T, 2>, heights: Vec<T>) -> Self {
+        assert_eq!(
+            heights.len(), n_cells(quantizer.n_levels),
+            "Heightmap needs one height per level"
+        );
+        Heightmap {
+            quantizer,
+            heights,
+            max_slope: None,
+            climb_weight: T::from_usize(0),
+            descent_weight: T::from_usize(0),
+            cost_scale: T::from_usize(1000),
+        }
+    }
+
+    // Sample `height` at every dequantized level
+    pub fn from_fn<F>(quantizer: Quantizer<T, 2>, mut height: F) -> Self
+    where
+        F: FnMut(&[T; 2]) -> T,
+    {
+        let heights = (0..n_cells(quantizer.n_levels))
+            .map(|index| height(&quantizer.dequantize(unflat_index(index, quantizer.n_levels))))
+            .collect();
+        Self::new(quantizer, heights)
+    }
+
+    pub fn with_max_slope(mut self, max_slope: T) -> Self {
+        self.max_slope = Some(max_slope);
+        self
+    }
+
+    pub fn with_climb_weight(mut self, climb_weight: T) -> Self {
+        self.climb_weight = climb_weight;
+        self
+    }
+
+    pub fn with_descent_weight(mut self, descent_weight: T) -> Self {
+        self.descent_weight = descent_weight;
+        self
+    }
+
+    pub fn with_cost_scale(mut self, cost_scale: T) -> Self {
+        self.cost_scale = cost_scale;
+        self
+    }
+
+    pub fn quantizer(&self) -> &Quantizer<T, 2> {
+        &self.quantizer
+    }
+
+    pub fn height(&self, n: [usize; 2]) -> Option<T> {
+        flat_index(n, self.quantizer.n_levels).map(|index| self.heights[index])
+    }
+
+    // Bilinear elevation at a world-space point
+    pub fn elevation(&self, x: [T; 2]) -> Option<T> {
+        wrapping_interpolate(
+            self.quantizer.cell_coordinate(x),
+            self.quantizer.n_levels,
+            self.quantizer.periodic(),
+            |n| self.height(n),
+        )
+    }
+
+    // World-space (x, y, z) of a level
+    pub fn waypoint(&self, n: [usize; 2]) -> Option<[T; 3]> {
+        let [x, y] = self.quantizer.dequantize(n);
+        self.height(n).map(|z| [x, y, z])
+    }
+
+    // Lift a planar path onto the terrain
+    pub fn lift(&self, path: &[[T; 2]]) -> Vec<[T; 3]> {
+        path.iter()
+            .filter_map(|&[x, y]| self.elevation([x, y]).map(|z| [x, y, z]))
+            .collect()
+    }
+
+    // Unscaled cost of stepping from `a` to `b`, None when too steep or off the map
+    pub fn step_cost(&self, a: [usize; 2], b: [usize; 2]) -> Option<T> {
+        let rise = self.height(b)? - self.height(a)?;
+        let run = self.horizontal_distance(a, b);
+        let zero = T::from_usize(0);
+        let (ascent, descent) = if rise >= zero { (rise, zero) } else { (zero, zero - rise) };
+        if let Some(max_slope) = self.max_slope
+            && run > zero
+            && ascent + descent > max_slope * run
+        {
+            return None;
+        }
+        Some(run + self.climb_weight * ascent + self.descent_weight * descent)
+    }
+
+    // Successors for `quantized_astar` through `offsets`, ex. `face_offsets`,
+    // wrapping around the periodic axes of the quantizer
+    pub fn successors(&self, n: &[usize; 2], offsets: &[[isize; 2]]) -> Vec<([usize; 2], u32)> {
+        let periodic = self.quantizer.periodic();
+        offsets.iter()
+            .filter_map(|&offset| {
+                let m = wrapping_offset_cell(*n, offset, self.quantizer.n_levels, periodic)?;
+                let cost = self.step_cost(*n, m)?;
+                Some((m, ceil_to_u32(cost * self.cost_scale)))
+            })
+            .collect()
+    }
+
+    // Scaled straight-line horizontal distance, never above the true cost
+    pub fn heuristic(&self, a: [usize; 2], b: [usize; 2]) -> u32 {
+        (self.horizontal_distance(a, b) * self.cost_scale).floor().to_usize() as u32
+    }
+
+    // The short way around periodic axes
+    fn horizontal_distance(&self, a: [usize; 2], b: [usize; 2]) -> T {
+        let pa = self.quantizer.dequantize(a);
+        let pb = self.quantizer.dequantize(b);
+        let [dx, dy] = self.quantizer.wrapped_difference(pa, pb);
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+// Rounding up keeps the floored straight-line heuristic admissible
+fn ceil_to_u32<T: TranscendentalFloat>(x: T) -> u32 {
+    let floor = x.floor();
+    let ceil = if floor < x { floor + T::from_usize(1) } else { floor };
+    ceil.to_usize() as u32
+}
@@ -0,0 +1,156 @@
+use crate::traits::{HasQuantizationMethods, FiniteFloat};
+use crate::utils::grid::{flat_index, n_cells, neighborhood_offsets, offset_cell, unflat_index};
+use crate::utils::quantizer::Quantizer;
+
+// Integer step costs, a unit step costs 1000 and diagonals are scaled by
+// sqrt(2) and sqrt(3) so that costs stay `Ord` for `quantized_astar`
+pub const STRAIGHT_COST: u32 = 1000;
+pub const DIAGONAL_COST: u32 = 1414;
+pub const CORNER_COST: u32 = 1732;
+
+// Which voxels count as neighbors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    // Sharing a face
+    Six,
+    // Sharing a face or an edge
+    Eighteen,
+    // Sharing a face, an edge or a corner
+    #[default]
+    TwentySix,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> Vec<[isize; 3]> {
+        neighborhood_offsets(self.max_axes())
+    }
+
+    fn max_axes(self) -> usize {
+        match self {
+            Connectivity::Six => 1,
+            Connectivity::Eighteen => 2,
+            Connectivity::TwentySix => 3,
+        }
+    }
+
+    // Exact cost between two voxels on an empty map, admissible and
+    // consistent as an A* heuristic
+    pub fn distance(self, a: [usize; 3], b: [usize; 3]) -> u32 {
+        let mut d = [0; 3];
+        for i in 0..3 {
+            d[i] = a[i].abs_diff(b[i]) as u32;
+        }
+        d.sort_unstable_by(|x, y| y.cmp(x));
+        let [d1, d2, d3] = d;
+        match self {
+            Connectivity::Six => STRAIGHT_COST * (d1 + d2 + d3),
+            Connectivity::Eighteen if d1 >= d2 + d3 => {
+                DIAGONAL_COST * (d2 + d3) + STRAIGHT_COST * (d1 - d2 - d3)
+            }
+            Connectivity::Eighteen => {
+                let sum = d1 + d2 + d3;
+                DIAGONAL_COST * (sum / 2) + STRAIGHT_COST * (sum % 2)
+            }
+            Connectivity::TwentySix => {
+                CORNER_COST * d3 + DIAGONAL_COST * (d2 - d3) + STRAIGHT_COST * (d1 - d2)
+            }
+        }
+    }
+}
+
+// Cost of a single step by `offset`
+pub fn step_cost(offset: [isize; 3]) -> u32 {
+    match offset.iter().filter(|&&o| o != 0).count() {
+        0 => 0,
+        1 => STRAIGHT_COST,
+        2 => DIAGONAL_COST,
+        _ => CORNER_COST,
+    }
+}
+
+// Dense occupancy over the voxels of a 3D quantizer
+#[derive(Debug, Clone)]
+pub struct VoxelMap<T: FiniteFloat> {
+    quantizer: Quantizer<T, 3>,
+    occupied: Vec<bool>,
+}
+
+impl<T: FiniteFloat> VoxelMap<T> {
+    // All voxels free
+    pub fn new(quantizer: Quantizer<T, 3>) -> Self {
+        let occupied = vec![false; n_cells(quantizer.n_levels)];
+        VoxelMap { quantizer, occupied }
+    }
+
+    // Voxels whose center satisfies `occupied`
+    pub fn from_fn<F>(quantizer: Quantizer<T, 3>, mut occupied: F) -> Self
+    where
+        F: FnMut(&[T; 3]) -> bool,
+    {
+        let mut map = Self::new(quantizer);
+        for index in 0..map.occupied.len() {
+            let n = unflat_index(index, map.quantizer.n_levels);
+            map.occupied[index] = occupied(&map.quantizer.dequantize(n));
+        }
+        map
+    }
+
+    pub fn quantizer(&self) -> &Quantizer<T, 3> {
+        &self.quantizer
+    }
+
+    pub fn set_occupied(&mut self, n: [usize; 3], occupied: bool) {
+        if let Some(index) = flat_index(n, self.quantizer.n_levels) {
+            self.occupied[index] = occupied;
+        }
+    }
+
+    // Mark the voxels from the one nearest to `min` to the one nearest to `max`
+    pub fn fill_box(&mut self, min: [T; 3], max: [T; 3], occupied: bool) {
+        let n_levels = self.quantizer.n_levels;
+        let lo = self.quantizer.quantize(min);
+        let hi = self.quantizer.quantize(max);
+        for x in lo[0]..=hi[0].min(n_levels[0].saturating_sub(1)) {
+            for y in lo[1]..=hi[1].min(n_levels[1].saturating_sub(1)) {
+                for z in lo[2]..=hi[2].min(n_levels[2].saturating_sub(1)) {
+                    self.set_occupied([x, y, z], occupied);
+                }
+            }
+        }
+    }
+
+    // Off-grid voxels count as occupied
+    pub fn is_occupied(&self, n: [usize; 3]) -> bool {
+        flat_index(n, self.quantizer.n_levels).is_none_or(|index| self.occupied[index])
+    }
+
+    pub fn is_free(&self, n: [usize; 3]) -> bool {
+        !self.is_occupied(n)
+    }
+
+    // Whether the world-space point lies in an occupied voxel
+    pub fn contains(&self, x: [T; 3]) -> bool {
+        self.is_occupied(self.quantizer.quantize(x))
+    }
+
+    // Free neighbors of `n`. Diagonal steps are not allowed to cut corners,
+    // every voxel they brush past has to be free as well.
+    pub fn neighbors(&self, n: &[usize; 3], connectivity: Connectivity) -> Vec<[usize; 3]> {
+        self.successors(n, connectivity).into_iter().map(|(m, _)| m).collect()
+    }
+
+    // Successors for `quantized_astar` with `step_cost` costs
+    pub fn successors(&self, n: &[usize; 3], connectivity: Connectivity) -> Vec<([usize; 3], u32)> {
+        let n_levels = self.quantizer.n_levels;
+        connectivity.offsets().into_iter()
+            .filter_map(|offset| {
+                let m = offset_cell(*n, offset, n_levels)?;
+                let clear = (1..8usize).all(|mask| {
+                    let partial = [0, 1, 2].map(|i| if mask >> i & 1 == 1 { offset[i] } else { 0 });
+                    offset_cell(*n, partial, n_levels).is_some_and(|c| self.is_free(c))
+                });
+                clear.then(|| (m, step_cost(offset)))
+            })
+            .collect()
+    }
+}
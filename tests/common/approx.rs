@@ -0,0 +1,4 @@
+// Equal up to float rounding, for results of trigonometry or matrix inversion
+pub fn close<const DIM: usize>(x: [f64; DIM], y: [f64; DIM]) -> bool {
+    (0..DIM).all(|i| (x[i] - y[i]).abs() < 1e-9)
+}
@@ -0,0 +1,58 @@
+use std::collections::HashSet;
+
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::{grid::*, quantizer::*},
+};
+
+// n x n cells one unit apart, starting at the origin
+pub fn square_quantizer(n: usize) -> Quantizer<f32, 2> {
+    let max = (n - 1) as f32;
+    Quantizer::with_n([0.0, 0.0], [max, max], [n, n])
+}
+
+pub fn manhattan(a: &[usize; 2], b: &[usize; 2]) -> u32 {
+    (a[0].abs_diff(b[0]) + a[1].abs_diff(b[1])) as u32
+}
+
+// Successors over the free cells of a quantizer's grid
+pub fn successors(
+    quantizer: Quantizer<f32, 2>,
+    free: impl Fn(&[usize; 2]) -> bool,
+) -> impl FnMut(&[usize; 2]) -> Vec<([usize; 2], u32)> {
+    move |n| {
+        grid_neighbors(&quantizer, *n, &face_offsets())
+            .into_iter()
+            .filter(|m| free(m))
+            .map(|m| (m, 1))
+            .collect()
+    }
+}
+
+// Reference cost from a fresh search over the same blocked cells
+pub fn fresh_cost(
+    quantizer: &Quantizer<f32, 2>,
+    blocked: &HashSet<[usize; 2]>,
+    start: [f32; 2],
+    goal: [f32; 2],
+) -> Option<u32> {
+    let goal_n = quantizer.quantize(goal);
+    if blocked.contains(&quantizer.quantize(start)) {
+        return None;
+    }
+    quantized_astar(
+        quantizer,
+        start,
+        |&n| {
+            grid_neighbors(quantizer, n, &face_offsets())
+                .into_iter()
+                .filter(|m| !blocked.contains(m))
+                .map(|m| (m, 1))
+                .collect::<Vec<_>>()
+        },
+        |n| manhattan(n, &goal_n),
+        |n| *n == goal_n,
+    )
+    .map(|(_, cost)| cost)
+}
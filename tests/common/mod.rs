@@ -1,5 +1,16 @@
+// Not every test file uses every helper
+#![allow(dead_code, unused_imports)]
+
+mod approx;
+mod grid_search;
 mod mock_quantizer;
 mod pseudo_float;
+mod staggered_walls;
+mod timed_paths;
 
+pub use self::approx::*;
+pub use self::grid_search::*;
 pub use self::mock_quantizer::*;
 pub use self::pseudo_float::*;
+pub use self::staggered_walls::*;
+pub use self::timed_paths::*;
@@ -67,4 +67,8 @@ impl FiniteFloat for PseudoFloat {
     fn round(self) -> Self {
         Self { value: self.value.round() }
     }
+
+    fn floor(self) -> Self {
+        Self { value: self.value.floor() }
+    }
 }
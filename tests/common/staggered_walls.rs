@@ -0,0 +1,39 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::{grid::*, quantizer::*},
+};
+
+// A 30 x 30 map with staggered walls that trap a greedy search. Moves go to
+// the 8 neighbors at 10 straight and 14 diagonal, the goal is the far corner.
+pub const WALLS_N: usize = 30;
+pub const WALLS_GOAL: [usize; 2] = [WALLS_N - 1, WALLS_N - 1];
+
+pub fn walls_quantizer() -> Quantizer<f32, 2> {
+    Quantizer::with_n([0.0, 0.0], [29.0, 29.0], [WALLS_N, WALLS_N])
+}
+
+pub fn walls_free(&[x, y]: &[usize; 2]) -> bool {
+    !((x == 10 && y < 25) || (x == 20 && y > 4) || (y == 15 && (12..19).contains(&x)))
+}
+
+pub fn walls_successors(n: &[usize; 2]) -> Vec<([usize; 2], u32)> {
+    neighborhood_offsets::<2>(2).into_iter()
+        .filter_map(|offset| offset_cell(*n, offset, [WALLS_N, WALLS_N]).map(|m| (m, offset)))
+        .filter(|(m, _)| walls_free(m))
+        .map(|(m, [dx, dy])| (m, if dx != 0 && dy != 0 { 14 } else { 10 }))
+        .collect()
+}
+
+// Exact on an open map, so consistent
+pub fn walls_octile(&[x, y]: &[usize; 2]) -> u32 {
+    let dx = x.abs_diff(WALLS_GOAL[0]) as u32;
+    let dy = y.abs_diff(WALLS_GOAL[1]) as u32;
+    10 * dx.max(dy) + 4 * dx.min(dy)
+}
+
+pub fn walls_optimal() -> u32 {
+    quantized_astar(&walls_quantizer(), [0.0, 0.0], walls_successors, walls_octile, |n| *n == WALLS_GOAL)
+        .unwrap()
+        .1
+}
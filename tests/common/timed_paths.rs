@@ -0,0 +1,24 @@
+use quantized_pathfinding::directed::*;
+
+// Waypoint of an agent at time step `t`, staying at its goal after arriving
+pub fn at(path: &TimedPath<f32, 2>, t: usize) -> [f32; 2] {
+    path.get(t).unwrap_or(path.last().unwrap()).1
+}
+
+// No two agents share a cell or swap cells at any time step
+pub fn assert_conflict_free<P: AsRef<TimedPath<f32, 2>>>(paths: &[P]) {
+    let paths: Vec<&TimedPath<f32, 2>> = paths.iter().map(|p| p.as_ref()).collect();
+    let makespan = paths.iter().map(|p| p.len()).max().unwrap();
+    for t in 0..makespan {
+        for i in 0..paths.len() {
+            for j in i + 1..paths.len() {
+                assert_ne!(at(paths[i], t), at(paths[j], t), "vertex conflict at t={t}");
+                if t > 0 {
+                    let swapped = at(paths[i], t - 1) == at(paths[j], t)
+                        && at(paths[j], t - 1) == at(paths[i], t);
+                    assert!(!swapped || at(paths[i], t) == at(paths[i], t - 1), "edge conflict at t={t}");
+                }
+            }
+        }
+    }
+}
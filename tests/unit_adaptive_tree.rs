@@ -0,0 +1,145 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::{adaptive_tree::*, quantizer::*},
+};
+
+const N: usize = 16;
+
+// A single 2x2 block in an otherwise empty 16x16 map
+fn blocked(&[x, y]: &[usize; 2]) -> bool {
+    (6..8).contains(&x) && (6..8).contains(&y)
+}
+
+fn tree() -> AdaptiveTree<f32, 2> {
+    let fine = Quantizer::with_n([0.0, 0.0], [15.0, 15.0], [N, N]);
+    AdaptiveTree::new(fine, blocked)
+}
+
+fn distance(a: &[f32; 2], b: &[f32; 2]) -> u32 {
+    (((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt() * 100.0).round() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leaves_cover_grid() {
+        let tree = tree();
+        let area: usize = tree.leaves().iter().map(|l| l.size * l.size).sum();
+        assert_eq!(area, N * N);
+        // Far fewer nodes than fine cells
+        assert!(tree.leaves().len() < N * N / 8);
+        let blocked_area: usize = tree.leaves().iter()
+            .filter(|l| !l.free)
+            .map(|l| l.size * l.size)
+            .sum();
+        assert_eq!(blocked_area, 4);
+    }
+
+    #[test]
+    fn test_subdivides_only_near_obstacles() {
+        let tree = tree();
+        assert_eq!(tree.leaf([12, 12]), Leaf { corner: [8, 8], size: 8, free: true });
+        assert_eq!(tree.leaf([7, 7]), Leaf { corner: [6, 6], size: 2, free: false });
+        assert!(tree.leaf([5, 5]).size < 8);
+        assert!(!tree.is_free([6, 7]));
+        assert!(tree.is_free([8, 7]));
+    }
+
+    #[test]
+    fn test_dequantize_leaf_center() {
+        let tree = tree();
+        assert_eq!(tree.quantize([13.2, 9.9]), [8, 8]);
+        assert_eq!(tree.dequantize([8, 8]), [11.5, 11.5]);
+        // Any fine cell of the leaf gives the same center
+        assert_eq!(tree.dequantize([15, 9]), [11.5, 11.5]);
+        assert_eq!(tree.n_levels(), [N, N]);
+    }
+
+    #[test]
+    fn test_neighbors_share_faces() {
+        let tree = tree();
+        let neighbors = tree.neighbors(&[8, 8]);
+        assert!(!neighbors.is_empty());
+        for m in &neighbors {
+            let leaf = tree.leaf(*m);
+            assert!(leaf.free);
+            assert!(leaf.corner[0] + leaf.size == 8 || leaf.corner[1] + leaf.size == 8);
+        }
+        // Symmetric adjacency
+        for m in &neighbors {
+            assert!(tree.neighbors(m).contains(&[8, 8]));
+        }
+        // Blocked leaves are never entered or left
+        assert!(tree.neighbors(&[6, 6]).is_empty());
+        let all_free = tree.leaves().iter()
+            .flat_map(|l| tree.neighbors(&l.corner))
+            .all(|m| tree.is_free(m));
+        assert!(all_free);
+    }
+
+    #[test]
+    fn test_astar_over_leaves() {
+        let tree = tree();
+        let goal = tree.quantize([15.0, 15.0]);
+        let goal_center = tree.dequantize(goal);
+        let result = quantized_astar(
+            &tree,
+            [0.0, 0.0],
+            |n| tree.successors(n, distance),
+            |n| distance(&tree.dequantize(*n), &goal_center),
+            |n| *n == goal,
+        );
+        let (path, _) = result.unwrap();
+        assert_eq!(path.last(), Some(&goal_center));
+        for p in &path {
+            assert!(tree.is_free(tree.quantize(*p)));
+        }
+        // Large leaves keep the path short
+        assert!(path.len() < N);
+    }
+
+    #[test]
+    fn test_walled_off_goal() {
+        // Wall across the whole map at x = 8
+        let fine = Quantizer::with_n([0.0, 0.0], [15.0, 15.0], [N, N]);
+        let tree = AdaptiveTree::new(fine, |&[x, _]: &[usize; 2]| x == 8);
+        let goal = tree.quantize([15.0, 0.0]);
+        let result = quantized_astar(
+            &tree,
+            [0.0, 0.0],
+            |n| tree.successors(n, distance),
+            |_| 0u32,
+            |n| *n == goal,
+        );
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_octree_non_power_of_two() {
+        let fine = Quantizer::with_n([0.0; 3], [4.0; 3], [5, 5, 5]);
+        let tree = AdaptiveTree::new(fine, |_: &[usize; 3]| false);
+        let free_volume: usize = tree.leaves().iter()
+            .filter(|l| l.free)
+            .map(|l| l.size.pow(3))
+            .sum();
+        assert_eq!(free_volume, 125);
+        assert_eq!(tree.leaf([2, 2, 2]), Leaf { corner: [0, 0, 0], size: 4, free: true });
+        assert_eq!(tree.neighbors(&[4, 4, 4]).len(), 3);
+    }
+
+    #[test]
+    fn test_thin_strip_skips_padding() {
+        // Padded to a 4096 x 4096 cube, of which only one row is on the grid
+        let fine = Quantizer::with_n([0.0, 0.0], [4095.0, 0.0], [4096, 1]);
+        let tree = AdaptiveTree::new(fine, |_: &[usize; 2]| false);
+        let free_area: usize = tree.leaves().iter()
+            .filter(|l| l.free)
+            .map(|l| l.size * l.size)
+            .sum();
+        assert_eq!(free_area, 4096);
+        assert_eq!(tree.leaf([0, 3000]), Leaf { corner: [0, 2048], size: 2048, free: false });
+    }
+}
@@ -0,0 +1,76 @@
+use quantized_pathfinding::{
+    traits::*,
+    utils::affine_quantizer::*,
+    utils::quantizer::*,
+};
+
+// common for testing
+mod common;
+use common::close;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translated() {
+        let local = Quantizer::with_n([0.0f64, 0.0], [4.0, 4.0], [5, 5]);
+        let quantizer = AffineQuantizer::translated(local, [10.0, -2.0]);
+
+        assert_eq!(quantizer.n_levels(), [5, 5]);
+        assert_eq!(quantizer.quantize([10.0, -2.0]), [0, 0]);
+        assert_eq!(quantizer.quantize([12.9, 0.2]), [3, 2]);
+        assert_eq!(quantizer.dequantize([4, 1]), [14.0, -1.0]);
+    }
+
+    #[test]
+    fn test_rotated_aisles() {
+        // Aisles running along the diagonal y = x
+        let local = Quantizer::with_n([0.0f64, 0.0], [10.0, 2.0], [11, 3]);
+        let angle = std::f64::consts::FRAC_PI_4;
+        let quantizer = AffineQuantizer::rotated(local, angle, [0.0, 0.0]);
+
+        let s = 2f64.sqrt() / 2.0;
+        assert!(close(quantizer.dequantize([1, 0]), [s, s]));
+        assert!(close(quantizer.dequantize([0, 1]), [-s, s]));
+        assert_eq!(quantizer.quantize([3.0 * s, 3.0 * s]), [3, 0]);
+        assert_eq!(quantizer.quantize([4.0 * s - s, 4.0 * s + s]), [4, 1]);
+
+        let c = quantizer.cell_coordinate([2.5 * s, 2.5 * s]);
+        assert!(close(c, [2.5, 0.0]));
+    }
+
+    #[test]
+    fn test_scale_and_shear_roundtrip() {
+        let local = Quantizer::with_n([0.0f64, 0.0, 0.0], [3.0, 3.0, 3.0], [4, 4, 4]);
+        let linear = [
+            [2.0, 0.5, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.3, 0.5],
+        ];
+        let quantizer = AffineQuantizer::new(local, linear, [1.0, 2.0, 3.0]);
+
+        for n in [[0, 0, 0], [1, 2, 3], [3, 3, 3], [2, 0, 1]] {
+            let x = quantizer.dequantize(n);
+            assert_eq!(quantizer.quantize(x), n);
+            assert!(close(quantizer.to_local(x), local.dequantize(n)));
+            assert!(close(quantizer.to_world(quantizer.to_local(x)), x));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_singular_frame() {
+        let local = Quantizer::with_n([0.0f32, 0.0], [1.0, 1.0], [2, 2]);
+        AffineQuantizer::new(local, [[1.0, 2.0], [2.0, 4.0]], [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_periodic_local_axis() {
+        let local = Quantizer::with_n([0.0f32, 0.0], [4.0, 4.0], [5, 4]).with_periodic([false, true]);
+        let quantizer = AffineQuantizer::translated(local, [1.0, 1.0]);
+
+        assert_eq!(quantizer.periodic(), [false, true]);
+        assert_eq!(quantizer.quantize([1.0, 5.0]), [0, 0]);
+    }
+}
@@ -0,0 +1,85 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+};
+
+// common for testing
+mod common;
+use common::{walls_free, walls_octile, walls_optimal, walls_quantizer, walls_successors, WALLS_GOAL};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solutions_improve_within_bounds() {
+        let optimal = walls_optimal();
+        let solutions: Vec<_> = AraStar::new(
+            walls_quantizer(), [0.0, 0.0], walls_successors, walls_octile, |n| *n == WALLS_GOAL, |c| c as f32,
+        ).collect();
+
+        assert!(solutions.len() > 1);
+        for pair in solutions.windows(2) {
+            assert!(pair[1].cost <= pair[0].cost);
+            assert!(pair[1].bound <= pair[0].bound);
+        }
+        for solution in &solutions {
+            assert!(solution.bound >= 1.0);
+            assert!(solution.cost as f32 <= solution.bound * optimal as f32 + 1e-3);
+            assert_eq!(solution.path[0], [0.0, 0.0]);
+            assert_eq!(solution.path.last(), Some(&[29.0, 29.0]));
+            assert!(solution.path.iter().all(|p| walls_free(&walls_quantizer().quantize(*p))));
+        }
+        let last = solutions.last().unwrap();
+        assert_eq!(last.bound, 1.0);
+        assert_eq!(last.cost, optimal);
+    }
+
+    #[test]
+    fn test_first_solution_is_immediate() {
+        let mut planner = AraStar::new(
+            walls_quantizer(), [0.0, 0.0], walls_successors, walls_octile, |n| *n == WALLS_GOAL, |c| c as f32,
+        ).with_inflation(5.0, 1.0);
+        assert_eq!(planner.epsilon(), 5.0);
+        let first = planner.next().unwrap();
+        assert!(first.bound <= 5.0);
+        assert_eq!(planner.epsilon(), 4.0);
+    }
+
+    #[test]
+    fn test_no_inflation_is_optimal() {
+        let solutions: Vec<_> = AraStar::new(
+            walls_quantizer(), [0.0, 0.0], walls_successors, walls_octile, |n| *n == WALLS_GOAL, |c| c as f32,
+        ).with_inflation(1.0, 0.5).collect();
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].cost, walls_optimal());
+        assert_eq!(solutions[0].bound, 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inflation_step_must_be_positive() {
+        AraStar::new(
+            walls_quantizer(), [0.0, 0.0], walls_successors, walls_octile, |n| *n == WALLS_GOAL, |c| c as f32,
+        ).with_inflation(2.0, 0.0);
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let mut planner = AraStar::new(
+            walls_quantizer(), [0.0, 0.0], walls_successors, walls_octile, |n| *n == [10, 0], |c| c as f32,
+        );
+        assert!(planner.next().is_none());
+        assert!(planner.next().is_none());
+    }
+
+    #[test]
+    fn test_start_is_goal() {
+        let solutions: Vec<_> = AraStar::new(
+            walls_quantizer(), [0.2, 0.1], walls_successors, |_| 0u32, |n| *n == [0, 0], |c| c as f32,
+        ).collect();
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].path, vec![[0.0, 0.0]]);
+        assert_eq!(solutions[0].cost, 0);
+    }
+}
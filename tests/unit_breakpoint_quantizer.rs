@@ -0,0 +1,84 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::breakpoint_quantizer::*,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakpoint_1d() {
+        // Fine near the dock at 0, coarse further away
+        let quantizer = BreakpointQuantizer::with_edges([vec![0.0f32, 0.5, 1.0, 2.0, 10.0]]);
+
+        assert_eq!(quantizer.n_levels(), [4]);
+        assert_eq!(quantizer.quantize([0.2]), [0]);
+        assert_eq!(quantizer.quantize([0.5]), [1]);
+        assert_eq!(quantizer.quantize([1.7]), [2]);
+        assert_eq!(quantizer.quantize([9.0]), [3]);
+        assert_eq!(quantizer.quantize_ieee754([9.0]), [3]);
+
+        assert_eq!(quantizer.dequantize([0]), [0.25]);
+        assert_eq!(quantizer.dequantize([2]), [1.5]);
+        assert_eq!(quantizer.dequantize([3]), [6.0]);
+    }
+
+    #[test]
+    fn test_out_of_range_is_clamped() {
+        let quantizer = BreakpointQuantizer::with_edges([vec![-1.0f64, 0.0, 3.0]]);
+
+        assert_eq!(quantizer.quantize([-5.0]), [0]);
+        assert_eq!(quantizer.quantize([3.0]), [1]);
+        assert_eq!(quantizer.quantize([100.0]), [1]);
+        assert_eq!(quantizer.dequantize([2]), [1.5]);
+        assert_eq!(quantizer.dequantize([usize::MAX]), [1.5]);
+    }
+
+    #[test]
+    fn test_cell_coordinate() {
+        let quantizer = BreakpointQuantizer::with_edges([
+            vec![0.0f64, 1.0, 3.0],
+            vec![0.0f64, 4.0],
+        ]);
+
+        assert_eq!(quantizer.n_levels(), [2, 1]);
+        assert_eq!(quantizer.cell_coordinate([0.5, 2.0]), [0.0, 0.0]);
+        assert_eq!(quantizer.cell_coordinate([2.0, 3.0]), [1.0, 0.25]);
+        assert_eq!(quantizer.cell_coordinate([1.0, 0.0]), [0.5, -0.5]);
+        assert_eq!(quantizer.residual([2.5, 1.0]), [0.5, -1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unsorted_edges() {
+        BreakpointQuantizer::with_edges([vec![0.0f32, 2.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_with_quantized_astar() {
+        let edges = vec![0.0f32, 1.0, 2.0, 4.0, 8.0];
+        let quantizer = BreakpointQuantizer::with_edges([edges.clone(), edges]);
+        let [nx, ny] = quantizer.n_levels();
+        let goal_n = quantizer.quantize([7.0, 7.0]);
+
+        let result = quantized_astar(
+            &quantizer,
+            [0.5, 0.5],
+            |&[x, y]| {
+                let mut neighbors = vec![];
+                if x + 1 < nx { neighbors.push(([x + 1, y], 1u32)); }
+                if y + 1 < ny { neighbors.push(([x, y + 1], 1u32)); }
+                neighbors
+            },
+            |_| 0,
+            |&n| n == goal_n,
+        );
+
+        let (path, cost) = result.unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(path.first(), Some(&[0.5, 0.5]));
+        assert_eq!(path.last(), Some(&[6.0, 6.0]));
+    }
+}
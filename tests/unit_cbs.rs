@@ -0,0 +1,73 @@
+use quantized_pathfinding::{
+    directed::*,
+    utils::quantizer::*,
+};
+
+// common for testing
+mod common;
+use common::{assert_conflict_free, manhattan, successors};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_through_pocket() {
+        // A 5 cell corridor with a single pocket above its middle
+        let quantizer = Quantizer::with_n([0.0, 0.0], [4.0, 1.0], [5, 2]);
+        let free = |&[x, y]: &[usize; 2]| y == 0 || x == 2;
+        let agents = [([0.0, 0.0], [4.0, 0.0]), ([4.0, 0.0], [0.0, 0.0])];
+        let (paths, cost) = quantized_cbs(
+            &quantizer, &agents, 20, 1, successors(quantizer, free), manhattan,
+        ).unwrap();
+
+        assert_conflict_free(&paths);
+        assert_eq!(cost, 11);
+        assert!(paths.iter().any(|p| p.iter().any(|&(_, x)| x == [2.0, 1.0])));
+        for (path, (start, goal)) in paths.iter().zip(agents) {
+            assert_eq!(path[0], (0, start));
+            assert_eq!(path.last().unwrap().1, goal);
+            assert!(path.iter().enumerate().all(|(k, &(t, _))| t == k));
+        }
+    }
+
+    #[test]
+    fn test_independent_agents() {
+        let quantizer = Quantizer::with_n([0.0, 0.0], [5.0, 5.0], [6, 6]);
+        let agents = [([0.0, 0.0], [0.0, 5.0]), ([5.0, 0.0], [5.0, 5.0])];
+        let (paths, cost) = quantized_cbs(
+            &quantizer, &agents, 20, 1, successors(quantizer, |_| true), manhattan,
+        ).unwrap();
+        assert_eq!(cost, 10);
+        assert_eq!(paths[0].len(), 6);
+        assert_eq!(paths[1].len(), 6);
+    }
+
+    #[test]
+    fn test_crossing_fleet() {
+        // Four robots crossing the middle of an open floor
+        let quantizer = Quantizer::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        let agents = [
+            ([0.0, 2.0], [4.0, 2.0]),
+            ([4.0, 2.0], [0.0, 2.0]),
+            ([2.0, 0.0], [2.0, 4.0]),
+            ([2.0, 4.0], [2.0, 0.0]),
+        ];
+        let (paths, cost) = quantized_cbs(
+            &quantizer, &agents, 20, 1, successors(quantizer, |_| true), manhattan,
+        ).unwrap();
+        assert_conflict_free(&paths);
+        assert_eq!(cost, 21);
+    }
+
+    #[test]
+    fn test_unsolvable() {
+        // Two agents in a dead-end corridor that need to pass each other
+        let quantizer = Quantizer::with_n([0.0, 0.0], [3.0, 0.0], [4, 1]);
+        let agents = [([0.0, 0.0], [3.0, 0.0]), ([3.0, 0.0], [0.0, 0.0])];
+        let result = quantized_cbs(
+            &quantizer, &agents, 6, 1, successors(quantizer, |_| true), manhattan,
+        );
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,69 @@
+use quantized_pathfinding::{
+    traits::*,
+    utils::companding_quantizer::*,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_levels_grow_geometrically() {
+        // u_max = ln(1 + 1023) spread over 11 levels: level i at 2^i - 1
+        let quantizer = LogQuantizer::with_n([0.0f64], [1023.0], [1.0], [11]);
+
+        assert_eq!(quantizer.n_levels(), [11]);
+        for i in 0..11 {
+            let [x] = quantizer.dequantize([i]);
+            assert!((x - (2f64.powi(i as i32) - 1.0)).abs() < 1e-9);
+        }
+        assert_eq!(quantizer.quantize([0.0]), [0]);
+        assert_eq!(quantizer.quantize([1.1]), [1]);
+        assert_eq!(quantizer.quantize([500.0]), [9]);
+        assert_eq!(quantizer.quantize([1023.0]), [10]);
+    }
+
+    #[test]
+    fn test_log_below_origin_clamps() {
+        let quantizer = LogQuantizer::with_n([10.0f32, 0.0], [100.0, 50.0], [1.0, 5.0], [8, 8]);
+
+        assert_eq!(quantizer.quantize([-3.0, -1.0]), [0, 0]);
+        assert_eq!(quantizer.dequantize([0, 0]), [10.0, 0.0]);
+    }
+
+    #[test]
+    fn test_log_finer_near_origin() {
+        let quantizer = LogQuantizer::with_n([0.0f64], [1000.0], [1.0], [32]);
+
+        let [near] = quantizer.residual([0.7]);
+        let [far] = quantizer.residual([700.0]);
+        assert!(near.abs() < 0.5);
+        assert!(far.abs() > 1.0);
+    }
+
+    #[test]
+    fn test_power_levels() {
+        // Quadratic: level i at 100 * (i / 10)^2
+        let quantizer = PowerQuantizer::with_n([0.0f64], [100.0], 2.0, [11]);
+
+        assert_eq!(quantizer.n_levels(), [11]);
+        assert!((quantizer.dequantize([1])[0] - 1.0).abs() < 1e-9);
+        assert!((quantizer.dequantize([5])[0] - 25.0).abs() < 1e-9);
+        assert!((quantizer.dequantize([10])[0] - 100.0).abs() < 1e-9);
+
+        assert_eq!(quantizer.quantize([1.2]), [1]);
+        assert_eq!(quantizer.quantize([24.0]), [5]);
+        assert_eq!(quantizer.quantize([-5.0]), [0]);
+        assert_eq!(quantizer.quantize_ieee754([24.0]), [4]);
+    }
+
+    #[test]
+    fn test_power_cell_coordinate() {
+        let quantizer = PowerQuantizer::with_n([1.0f32, 1.0], [5.0, 10.0], 1.0, [5, 10]);
+
+        // Exponent 1 is plain linear quantization
+        let [cx, cy] = quantizer.cell_coordinate([3.0, 5.5]);
+        assert!((cx - 2.0).abs() < 1e-5);
+        assert!((cy - 4.5).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,86 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::quantizer::*,
+};
+
+// Fixed-length moves that do not line up with the lattice
+const MOVES: [[f64; 2]; 4] = [[1.3, 0.0], [-1.3, 0.0], [0.0, 1.3], [0.0, -1.3]];
+
+fn inside(x: [f64; 2]) -> bool {
+    (0.0..=10.0).contains(&x[0]) && (0.0..=10.0).contains(&x[1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_keeps_exact_states() {
+        let quantizer = Quantizer::<f64, 2>::with_n([0.0, 0.0], [10.0, 10.0], [11, 11]);
+        let start = [0.35, 0.35];
+
+        let (path, cost) = quantized_continuous_astar(
+            &quantizer,
+            start,
+            |&[x, y]| {
+                MOVES.iter()
+                    .map(|&[dx, dy]| [x + dx, y + dy])
+                    .filter(|&next| inside(next))
+                    .map(|next| (next, 1u32))
+                    .collect::<Vec<_>>()
+            },
+            |_| 0,
+            |&[x, y]| (x - 5.0).abs() < 1.0 && (y - 5.0).abs() < 1.0,
+        ).unwrap();
+
+        assert_eq!(path[0], start);
+        assert_eq!(path.len() as u32, cost + 1);
+        // Every waypoint is start + k * 1.3 per axis, not a lattice point
+        for &[x, y] in path.iter() {
+            let kx = (x - 0.35) / 1.3;
+            let ky = (y - 0.35) / 1.3;
+            assert!((kx - kx.round()).abs() < 1e-9);
+            assert!((ky - ky.round()).abs() < 1e-9);
+        }
+        assert_eq!(cost, 6);
+        assert!(path.iter().all(|&x| quantizer.residual(x) != [0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_cheapest_state_per_cell() {
+        // A coarse grid merges many states; the search still finds the goal
+        let quantizer = Quantizer::<f32, 1>::with_n([0.0], [10.0], [3]);
+        let (path, cost) = quantized_continuous_astar(
+            &quantizer,
+            [0.0],
+            |&[x]| vec![([x + 1.0], 1u32), ([x + 4.5], 5u32)],
+            |_| 0,
+            |&[x]| x >= 9.0,
+        ).unwrap();
+
+        assert_eq!(path.len(), 3);
+        assert_eq!(cost, 10);
+        assert_eq!(path.last(), Some(&[9.0]));
+    }
+
+    #[test]
+    fn test_no_path() {
+        let quantizer = Quantizer::<f64, 2>::with_n([0.0, 0.0], [10.0, 10.0], [11, 11]);
+        let result = quantized_continuous_astar(
+            &quantizer,
+            [1.0, 1.0],
+            |&[x, y]| {
+                MOVES.iter()
+                    .map(|&[dx, dy]| [x + dx, y + dy])
+                    .filter(|&[nx, _]| nx < 4.0)
+                    .filter(|&next| inside(next))
+                    .map(|next| (next, 1u32))
+                    .collect::<Vec<_>>()
+            },
+            |_| 0,
+            |&[x, _]| x > 8.0,
+        );
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,104 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::quantizer::*,
+};
+
+const N_LEVELS: [usize; 2] = [5, 5];
+
+fn neighbors(&[x, y]: &[usize; 2], walls: &[[usize; 2]]) -> Vec<([usize; 2], u32)> {
+    let mut result = vec![];
+    for &[dx, dy] in &[[1, 0], [-1, 0], [0, 1], [0, -1]] {
+        let nx = x as i32 + dx;
+        let ny = y as i32 + dy;
+        if nx >= 0 && ny >= 0 &&
+           nx < N_LEVELS[0] as i32 &&
+           ny < N_LEVELS[1] as i32 &&
+           !walls.contains(&[nx as usize, ny as usize]) {
+            result.push(([nx as usize, ny as usize], 1));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_source_manhattan() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], N_LEVELS);
+        let field = quantized_dijkstra_field(&quantizer, [[0.0, 0.0]], |n| neighbors(n, &[]));
+
+        assert_eq!(field.n_levels, N_LEVELS);
+        assert_eq!(field.get([0, 0]), Some(0));
+        assert_eq!(field.get([4, 4]), Some(8));
+        assert_eq!(field.get([2, 3]), Some(5));
+        assert_eq!(field.get([5, 0]), None); // off the grid
+    }
+
+    #[test]
+    fn test_multi_source_takes_nearest() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], N_LEVELS);
+        let field = quantized_dijkstra_field(
+            &quantizer,
+            [[0.0, 0.0], [4.0, 4.0]],
+            |n| neighbors(n, &[]),
+        );
+
+        assert_eq!(field.get([0, 0]), Some(0));
+        assert_eq!(field.get([4, 4]), Some(0));
+        assert_eq!(field.get([4, 0]), Some(4));
+        assert_eq!(field.get([1, 1]), Some(2));
+        assert_eq!(field.get([3, 3]), Some(2));
+    }
+
+    #[test]
+    fn test_unreachable_cells() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], N_LEVELS);
+        let walls = [[2, 0], [2, 1], [2, 2], [2, 3], [2, 4]];
+        let field = quantized_dijkstra_field(&quantizer, [[0.0, 0.0]], |n| neighbors(n, &walls));
+
+        assert_eq!(field.get([1, 4]), Some(5));
+        assert_eq!(field.get([2, 2]), None);
+        assert_eq!(field.get([3, 0]), None);
+        assert_eq!(field.iter().count(), 10);
+    }
+
+    #[test]
+    fn test_sample_in_world_space() {
+        let quantizer = Quantizer::<f64, 2>::with_n([0.0, 0.0], [8.0, 8.0], N_LEVELS);
+        let field = quantized_dijkstra_field(&quantizer, [[0.1, -0.1]], |n| neighbors(n, &[]));
+
+        assert_eq!(field.sample(&quantizer, [0.0, 0.0]), Some(0));
+        assert_eq!(field.sample(&quantizer, [3.9, 2.1]), Some(3)); // [2, 1]
+        assert_eq!(field.sample(&quantizer, [8.0, 8.0]), Some(8));
+
+        let (position, cost) = field.iter_dequantized(&quantizer)
+            .max_by_key(|&(_, c)| c)
+            .unwrap();
+        assert_eq!(position, [8.0, 8.0]);
+        assert_eq!(cost, 8);
+    }
+
+    #[test]
+    fn test_field_as_heuristic() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], N_LEVELS);
+        let walls = [[1, 1], [2, 1], [3, 1], [1, 3], [2, 3], [3, 3]];
+        let goal = [4.0, 4.0];
+        let goal_n = quantizer.quantize(goal);
+        let field = quantized_dijkstra_field(&quantizer, [goal], |n| neighbors(n, &walls));
+
+        let (path, cost) = quantized_astar(
+            &quantizer,
+            [0.0, 0.0],
+            |n| neighbors(n, &walls),
+            |n| field.get(*n).unwrap_or(u32::MAX),
+            |n| *n == goal_n,
+        ).unwrap();
+
+        assert_eq!(cost, 8);
+        assert_eq!(field.get([0, 0]), Some(cost));
+        assert_eq!(path.last(), Some(&goal));
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::HashSet;
+
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::grid::*,
+};
+
+// common for testing
+mod common;
+use common::{fresh_cost, manhattan, square_quantizer};
+
+const N: usize = 12;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_grid() {
+        let mut planner = DStarLite::new(
+            square_quantizer(N), [0.0, 0.0], [11.0, 5.0], &face_offsets(), |_| Some(1u32), manhattan,
+        );
+        let (path, cost) = planner.plan().unwrap();
+        assert_eq!(cost, 16);
+        assert_eq!(path.len(), 17);
+        assert_eq!(path[0], [0.0, 0.0]);
+        assert_eq!(path.last(), Some(&[11.0, 5.0]));
+    }
+
+    #[test]
+    fn test_region_update_repairs_path() {
+        let mut planner = DStarLite::new(
+            square_quantizer(N), [0.0, 5.0], [11.0, 5.0], &face_offsets(), |_| Some(1u32), manhattan,
+        );
+        assert_eq!(planner.plan().unwrap().1, 11);
+
+        // Wall at x = 6 from y = 0 to y = 9
+        planner.update_region([5.8, -1.0], [6.2, 9.2], None);
+        let (path, cost) = planner.plan().unwrap();
+        let blocked: HashSet<_> = box_cells([6, 0], [6, 9], [N, N]).into_iter().collect();
+        assert_eq!(Some(cost), fresh_cost(&square_quantizer(N), &blocked, [0.0, 5.0], [11.0, 5.0]));
+        assert!(path.contains(&[6.0, 10.0]) || path.contains(&[6.0, 11.0]));
+
+        // Cleared again
+        planner.update_region([5.8, -1.0], [6.2, 9.2], Some(1));
+        assert_eq!(planner.plan().unwrap().1, 11);
+    }
+
+    #[test]
+    fn test_moving_start() {
+        let mut planner = DStarLite::new(
+            square_quantizer(N), [0.0, 0.0], [11.0, 11.0], &face_offsets(), |_| Some(1u32), manhattan,
+        );
+        let mut blocked = HashSet::new();
+        for k in 0..8 {
+            // Follow the current plan one step
+            let x = planner.plan().unwrap().0[1];
+            planner.move_start(x);
+            // Drop an obstacle ahead of the agent every other step
+            if k % 2 == 0 {
+                let ahead = planner.plan().unwrap().0[2];
+                let cell = planner.quantizer().quantize(ahead);
+                blocked.insert(cell);
+                planner.update_cell(cell, None);
+            }
+            let (replanned, cost) = planner.plan().unwrap();
+            assert_eq!(replanned[0], x);
+            assert_eq!(Some(cost), fresh_cost(&square_quantizer(N), &blocked, x, [11.0, 11.0]));
+        }
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let mut planner = DStarLite::new(
+            square_quantizer(N), [0.0, 0.0], [11.0, 11.0], &face_offsets(), |_| Some(1u32), manhattan,
+        );
+        assert!(planner.plan().is_some());
+        planner.update_cells([[10, 11], [11, 10]], None);
+        assert!(planner.plan().is_none());
+        assert_eq!(planner.cost_to_goal(planner.start()), None);
+        planner.update_cell([10, 11], Some(1));
+        assert_eq!(planner.plan().unwrap().1, 22);
+    }
+
+    #[test]
+    fn test_start_at_goal() {
+        let mut planner = DStarLite::new(
+            square_quantizer(N), [4.0, 4.0], [4.0, 4.0], &face_offsets(), |_| Some(1u32), manhattan,
+        );
+        assert_eq!(planner.plan(), Some((vec![[4.0, 4.0]], 0)));
+        planner.update_cell([4, 4], None);
+        assert_eq!(planner.plan(), None);
+    }
+
+    #[test]
+    fn test_matches_fresh_search_after_many_updates() {
+        let mut planner = DStarLite::new(
+            square_quantizer(N), [0.0, 0.0], [11.0, 11.0], &face_offsets(), |_| Some(1u32), manhattan,
+        );
+        let mut blocked = HashSet::new();
+        let mut seed = 7u64;
+        for _ in 0..60 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let cell = [(seed >> 33) as usize % N, (seed >> 45) as usize % N];
+            if cell == [0, 0] || cell == [N - 1, N - 1] {
+                continue;
+            }
+            let block = !blocked.contains(&cell);
+            if block {
+                blocked.insert(cell);
+            } else {
+                blocked.remove(&cell);
+            }
+            planner.update_cell(cell, if block { None } else { Some(1) });
+            let expected = fresh_cost(&square_quantizer(N), &blocked, [0.0, 0.0], [11.0, 11.0]);
+            assert_eq!(planner.plan().map(|(_, cost)| cost), expected);
+        }
+    }
+
+    #[test]
+    fn test_weighted_cells() {
+        // Entering the middle column is expensive
+        let mut planner = DStarLite::new(
+            square_quantizer(N), [0.0, 0.0], [2.0, 0.0], &face_offsets(),
+            |&[x, y]: &[usize; 2]| Some(if x == 1 && y < 3 { 10u32 } else { 1 }),
+            |_: &[usize; 2], _: &[usize; 2]| 0,
+        );
+        assert_eq!(planner.plan().unwrap().1, 8);
+        planner.update_cell([1, 0], Some(1));
+        assert_eq!(planner.plan().unwrap().1, 2);
+    }
+}
@@ -0,0 +1,116 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::quantizer::*,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_grid_points_to_goal() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        let flow = quantized_flow_field(&quantizer, [2.0, 2.0], |_| Some(1u32));
+
+        assert_eq!(flow.direction([2, 2]), Some([0.0, 0.0]));
+        assert_eq!(flow.direction([0, 2]), Some([1.0, 0.0]));
+        assert_eq!(flow.direction([2, 4]), Some([0.0, -1.0]));
+        assert_eq!(flow.next_cell([4, 2]), Some([3, 2]));
+        assert_eq!(flow.sample(&quantizer, [2.1, 0.2]), Some([0.0, 1.0]));
+        assert_eq!(flow.iter().count(), 25);
+    }
+
+    #[test]
+    fn test_blocked_and_unreachable_cells() {
+        let quantizer = Quantizer::<f64, 2>::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        // Wall along x = 2, with the goal on the right side
+        let flow = quantized_flow_field(&quantizer, [4.0, 0.0], |&[x, _]| {
+            if x == 2 { None } else { Some(1u32) }
+        });
+
+        assert_eq!(flow.direction([2, 1]), None);
+        assert_eq!(flow.direction([0, 0]), None);
+        assert!(flow.direction([3, 4]).is_some());
+        assert!(flow.path_from(&quantizer, [0.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn test_costly_cells_are_avoided() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [2.0, 2.0], [3, 3]);
+        // Cheap ring around an expensive center
+        let flow = quantized_flow_field(&quantizer, [2.0, 1.0], |&n| {
+            if n == [1, 1] { Some(10u32) } else { Some(1) }
+        });
+
+        let path = flow.path_from(&quantizer, [0.0, 1.0]).unwrap();
+        assert_eq!(path.len(), 5);
+        assert!(!path.contains(&[1.0, 1.0]));
+        assert_eq!(path.last(), Some(&[2.0, 1.0]));
+    }
+
+    #[test]
+    fn test_from_distance_field_matches_astar() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [10.0, 10.0], [6, 6]);
+        let goal = [10.0, 10.0];
+        let goal_n = quantizer.quantize(goal);
+        let neighbors = |&[x, y]: &[usize; 2]| {
+            let mut result = vec![];
+            for &[dx, dy] in &[[1, 0], [-1, 0], [0, 1], [0, -1]] {
+                let nx = x as i32 + dx;
+                let ny = y as i32 + dy;
+                if (0..6).contains(&nx) && (0..6).contains(&ny) && !(ny == 3 && nx < 5) {
+                    result.push(([nx as usize, ny as usize], 1u32));
+                }
+            }
+            result
+        };
+
+        let field = quantized_dijkstra_field(&quantizer, [goal], neighbors);
+        let flow = FlowField::from_distance_field(&quantizer, &field);
+        let (astar_path, cost) = quantized_astar(
+            &quantizer, [0.0, 0.0], neighbors, |_| 0, |n| *n == goal_n,
+        ).unwrap();
+        let flow_path = flow.path_from(&quantizer, [0.0, 0.0]).unwrap();
+
+        assert_eq!(flow_path.len(), astar_path.len());
+        assert_eq!(flow_path.len() - 1, cost as usize);
+        assert_eq!(flow_path.last(), astar_path.last());
+    }
+
+    #[test]
+    fn test_zero_cost_cells_are_not_sinks() {
+        let quantizer = Quantizer::<f32, 1>::with_n([0.0], [9.0], [10]);
+        let flow = quantized_flow_field(&quantizer, [0.0], |&[x]| {
+            if x == 4 || x == 5 { Some(0u32) } else { Some(1) }
+        });
+
+        assert_ne!(flow.next_cell([5]), Some([5]));
+        assert_ne!(flow.next_cell([4]), Some([4]));
+        let path = flow.path_from(&quantizer, [8.0]).unwrap();
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.last(), Some(&[0.0]));
+    }
+
+    #[test]
+    fn test_periodic_seam() {
+        let quantizer = Quantizer::<f32, 1>::with_n([0.0], [8.0], [8]).with_periodic([true]);
+        let flow = quantized_flow_field(&quantizer, [0.0], |_| Some(1u32));
+
+        assert_eq!(flow.next_cell([7]), Some([0]));
+        assert_eq!(flow.direction([7]), Some([1.0]));
+        assert_eq!(flow.direction([6]), Some([1.0]));
+        assert_eq!(flow.direction([1]), Some([-1.0]));
+        assert_eq!(flow.path_from(&quantizer, [6.0]).unwrap(), vec![[6.0], [7.0], [0.0]]);
+    }
+
+    #[test]
+    fn test_blocked_goal_gives_empty_field() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        let flow = quantized_flow_field(&quantizer, [2.0, 2.0], |&n| {
+            if n == [2, 2] { None } else { Some(1u32) }
+        });
+
+        assert_eq!(flow.iter().count(), 0);
+    }
+}
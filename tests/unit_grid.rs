@@ -0,0 +1,105 @@
+use quantized_pathfinding::{
+    directed::*,
+    utils::grid::*,
+    utils::quantizer::*,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_index_roundtrip() {
+        let n_levels = [3, 4, 2];
+
+        assert_eq!(n_cells(n_levels), 24);
+        assert_eq!(flat_index([0, 0, 0], n_levels), Some(0));
+        assert_eq!(flat_index([1, 0, 0], n_levels), Some(1));
+        assert_eq!(flat_index([0, 1, 0], n_levels), Some(3));
+        assert_eq!(flat_index([2, 3, 1], n_levels), Some(23));
+        assert_eq!(flat_index([3, 0, 0], n_levels), None);
+
+        for i in 0..24 {
+            assert_eq!(flat_index(unflat_index(i, n_levels), n_levels), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_face_offsets() {
+        assert_eq!(face_offsets::<2>(), vec![[1, 0], [-1, 0], [0, 1], [0, -1]]);
+        assert_eq!(face_offsets::<3>().len(), 6);
+    }
+
+    #[test]
+    fn test_box_cells() {
+        let cells = box_cells([3, 1], [1, 2], [10, 10]);
+        assert_eq!(cells.len(), 6);
+        assert_eq!(cells[0], [1, 1]);
+        assert_eq!(cells[5], [3, 2]);
+        // Clipped to the grid
+        assert_eq!(box_cells([8, 8], [12, 9], [10, 10]).len(), 4);
+        assert!(box_cells([12, 0], [14, 0], [10, 10]).is_empty());
+    }
+
+    #[test]
+    fn test_wrapping_box_cells() {
+        let periodic = [true, false];
+        let cells = wrapping_box_cells([6, 1], [1, 2], [8, 10], periodic);
+        assert_eq!(cells.len(), 8);
+        assert_eq!(cells[0], [6, 1]);
+        assert_eq!(cells[2], [0, 1]);
+        assert_eq!(cells[3], [1, 1]);
+        assert_eq!(wrapping_box_cells([1, 1], [6, 2], [8, 10], periodic).len(), 12);
+        assert_eq!(wrapping_box_cells([3, 0], [3, 0], [8, 10], periodic), vec![[3, 0]]);
+    }
+
+    #[test]
+    fn test_neighborhood_offsets() {
+        assert_eq!(neighborhood_offsets::<3>(1).len(), 6);
+        assert_eq!(neighborhood_offsets::<3>(2).len(), 18);
+        assert_eq!(neighborhood_offsets::<3>(3).len(), 26);
+        assert_eq!(neighborhood_offsets::<2>(2).len(), 8);
+        assert!(!neighborhood_offsets::<3>(3).contains(&[0, 0, 0]));
+    }
+
+    #[test]
+    fn test_offset_cell_bounds() {
+        assert_eq!(offset_cell([0, 0], [-1, 0], [3, 3]), None);
+        assert_eq!(offset_cell([2, 2], [0, 1], [3, 3]), None);
+        assert_eq!(offset_cell([1, 1], [1, -1], [3, 3]), Some([2, 0]));
+    }
+
+    #[test]
+    fn test_wrapping_offset_cell() {
+        let periodic = [true, false];
+
+        assert_eq!(wrapping_offset_cell([0, 0], [-1, 0], [4, 3], periodic), Some([3, 0]));
+        assert_eq!(wrapping_offset_cell([3, 1], [1, 1], [4, 3], periodic), Some([0, 2]));
+        assert_eq!(wrapping_offset_cell([3, 2], [1, 1], [4, 3], periodic), None);
+        assert_eq!(wrapping_offset_cell([1, 0], [-6, 0], [4, 3], periodic), Some([3, 0]));
+    }
+
+    #[test]
+    fn test_grid_neighbors_on_a_torus() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 2.0], [4, 2])
+            .with_periodic([true, true]);
+
+        let mut neighbors = grid_neighbors(&quantizer, [0, 0], &face_offsets::<2>());
+        neighbors.sort();
+        // +y and -y both land on row 1
+        assert_eq!(neighbors, vec![[0, 1], [1, 0], [3, 0]]);
+    }
+
+    #[test]
+    fn test_flow_field_across_the_seam() {
+        let quantizer = Quantizer::<f32, 1>::with_n([0.0], [10.0], [10])
+            .with_periodic([true]);
+        let flow = quantized_flow_field(&quantizer, [0.0], |_| Some(1u32));
+
+        // Level 9 reaches level 0 through the seam in one step
+        assert_eq!(flow.next_cell([9]), Some([0]));
+        assert_eq!(flow.next_cell([6]), Some([7]));
+        assert_eq!(flow.next_cell([4]), Some([3]));
+        assert_eq!(flow.path_from(&quantizer, [8.0]).unwrap().len(), 3);
+    }
+}
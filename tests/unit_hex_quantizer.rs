@@ -0,0 +1,90 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::hex_quantizer::*,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let quantizer = HexQuantizer::with_n([0.0f64, 0.0], 1.0, [6, 6]);
+
+        assert_eq!(quantizer.n_levels(), [6, 6]);
+        for q in 0..6 {
+            for r in 0..6 {
+                let x = quantizer.dequantize([q, r]);
+                assert_eq!(quantizer.quantize(x), [q, r]);
+            }
+        }
+        let [x, y] = quantizer.dequantize([0, 1]);
+        assert!((x - 3f64.sqrt() / 2.0).abs() < 1e-9);
+        assert!((y - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_neighbors_are_equidistant() {
+        let quantizer = HexQuantizer::with_n([2.0f64, -1.0], 0.5, [5, 5]);
+        let center = quantizer.dequantize([2, 2]);
+        let neighbors = hex_neighbors(&quantizer, [2, 2]);
+
+        assert_eq!(neighbors.len(), 6);
+        for n in neighbors {
+            let [x, y] = quantizer.dequantize(n);
+            let d = ((x - center[0]).powi(2) + (y - center[1]).powi(2)).sqrt();
+            assert!((d - quantizer.spacing()).abs() < 1e-9);
+            assert_eq!(hex_distance(n, [2, 2]), 1);
+        }
+        assert_eq!(hex_neighbors(&quantizer, [0, 0]).len(), 2);
+    }
+
+    #[test]
+    fn test_cube_rounding() {
+        let quantizer = HexQuantizer::with_n([0.0f32, 0.0], 1.0, [4, 4]);
+
+        // Near the center but off the lattice
+        let [x, y] = quantizer.dequantize([1, 1]);
+        assert_eq!(quantizer.quantize([x + 0.4, y - 0.3]), [1, 1]);
+        // Per-axis rounding of the fractional axial coordinate would disagree here
+        let c = quantizer.cell_coordinate([1.1085, 0.72]); // ~[0.4, 0.48]
+        assert_eq!([c[0].round() as usize, c[1].round() as usize], [0, 0]);
+        assert_eq!(quantizer.quantize([1.1085, 0.72]), [0, 1]);
+        assert_eq!(quantizer.quantize([-5.0, -5.0]), [0, 0]);
+    }
+
+    #[test]
+    fn test_hex_distance() {
+        assert_eq!(hex_distance([0, 0], [0, 0]), 0);
+        assert_eq!(hex_distance([0, 0], [3, 0]), 3);
+        assert_eq!(hex_distance([3, 0], [0, 3]), 3);
+        assert_eq!(hex_distance([0, 0], [2, 2]), 4);
+    }
+
+    #[test]
+    fn test_with_quantized_astar() {
+        let quantizer = HexQuantizer::with_n([0.0f32, 0.0], 1.0, [8, 8]);
+        let goal = quantizer.dequantize([6, 5]);
+        let goal_n = quantizer.quantize(goal);
+        let walls = [[3, 2], [3, 3], [3, 4], [2, 5]];
+
+        let (path, cost) = quantized_astar(
+            &quantizer,
+            [0.0, 0.0],
+            |&n| {
+                hex_neighbors(&quantizer, n)
+                    .into_iter()
+                    .filter(|m| !walls.contains(m))
+                    .map(|m| (m, 1u32))
+                    .collect::<Vec<_>>()
+            },
+            |&n| hex_distance(n, goal_n) as u32,
+            |&n| n == goal_n,
+        ).unwrap();
+
+        assert!(cost >= hex_distance([0, 0], goal_n) as u32);
+        assert_eq!(path.len() as u32, cost + 1);
+        assert_eq!(path.last(), Some(&goal));
+    }
+}
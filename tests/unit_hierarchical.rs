@@ -0,0 +1,115 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::quantizer::*,
+};
+
+const N: usize = 24;
+
+// Vertical walls with a single gap each, alternating top and bottom
+fn passable(&[x, y]: &[usize; 2]) -> bool {
+    !(x % 6 == 5 && if (x / 6) % 2 == 0 { y < N - 2 } else { y > 1 })
+}
+
+fn neighbors(&[x, y]: &[usize; 2]) -> Vec<([usize; 2], u32)> {
+    let mut result = vec![];
+    for &[dx, dy] in &[[1, 0], [-1, 0], [0, 1], [0, -1]] {
+        let nx = x as i32 + dx;
+        let ny = y as i32 + dy;
+        if nx >= 0 && ny >= 0 && nx < N as i32 && ny < N as i32 {
+            let m = [nx as usize, ny as usize];
+            if passable(&m) {
+                result.push((m, 1));
+            }
+        }
+    }
+    result
+}
+
+fn planner(quantizer: Quantizer<f32, 2>) -> HierarchicalPlanner<f32, u32, 2> {
+    HierarchicalPlanner::new(quantizer, [8, 8], |n| passable(n).then_some(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coarse_quantizer_matches_clusters() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [23.0, 23.0], [N, N]);
+        let planner = planner(quantizer);
+        let coarse = planner.coarse_quantizer();
+
+        assert_eq!(coarse.n_levels, [3, 3]);
+        for n in [[0, 0], [7, 8], [8, 7], [23, 23], [15, 16]] {
+            let x = quantizer.dequantize(n);
+            assert_eq!(coarse.quantize(x), planner.cluster_of(n));
+        }
+        assert!(planner.portals().count() > 0);
+    }
+
+    #[test]
+    fn test_path_is_valid_and_near_optimal() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [23.0, 23.0], [N, N]);
+        let planner = planner(quantizer);
+        let start = [0.0, 0.0];
+        let goal = [23.0, 0.0];
+        let goal_n = quantizer.quantize(goal);
+
+        let (path, cost) = planner.plan(start, goal).unwrap();
+        let (_, optimal) = quantized_astar(
+            &quantizer, start, neighbors, |_| 0, |&n| n == goal_n,
+        ).unwrap();
+
+        assert!(cost >= optimal);
+        assert!(cost <= optimal + optimal / 2);
+        assert_eq!(path.len() as u32, cost + 1);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        for pair in path.windows(2) {
+            let [a, b] = [quantizer.quantize(pair[0]), quantizer.quantize(pair[1])];
+            assert!(passable(&b));
+            assert_eq!(a[0].abs_diff(b[0]) + a[1].abs_diff(b[1]), 1);
+        }
+    }
+
+    #[test]
+    fn test_same_cluster() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [23.0, 23.0], [N, N]);
+        let planner = planner(quantizer);
+        let (path, cost) = planner.plan([1.0, 1.0], [4.0, 3.0]).unwrap();
+
+        assert_eq!(cost, 5);
+        assert_eq!(path.len(), 6);
+    }
+
+    #[test]
+    fn test_same_cluster_detour() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [23.0, 23.0], [N, N]);
+        // Start and goal share a cluster but the wall splits it
+        let planner = HierarchicalPlanner::<f32, u32, 2>::new(
+            quantizer,
+            [12, 12],
+            |n| passable(n).then_some(1),
+        );
+        let (path, cost) = planner.plan([2.0, 0.0], [8.0, 0.0]).unwrap();
+
+        // Up through the gap at y = 22 and back down, 22 + 6 + 22
+        assert_eq!(path.last(), Some(&[8.0, 0.0]));
+        assert_eq!(cost, 50);
+    }
+
+    #[test]
+    fn test_blocked_or_unreachable() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [23.0, 23.0], [N, N]);
+        let planner = planner(quantizer);
+        assert!(planner.plan([5.0, 0.0], [20.0, 20.0]).is_none()); // start in a wall
+
+        let sealed = HierarchicalPlanner::<f32, u32, 2>::new(
+            quantizer,
+            [8, 8],
+            |&[x, y]| (x != 12 && passable(&[x, y])).then_some(1),
+        );
+        assert!(sealed.plan([0.0, 0.0], [23.0, 23.0]).is_none());
+    }
+}
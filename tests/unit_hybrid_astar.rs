@@ -0,0 +1,109 @@
+use std::f64::consts::PI;
+use quantized_pathfinding::{
+    directed::*,
+    utils::quantizer::*,
+};
+
+fn primitives() -> Vec<SteeringPrimitive<f64, u32>> {
+    [-0.5, 0.0, 0.5].iter()
+        .map(|&curvature| SteeringPrimitive { curvature, length: 1.0, cost: 10 })
+        .collect()
+}
+
+fn distance(a: [f64; 3], b: [f64; 2]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulate_arc() {
+        let [x, y, theta] = simulate_arc([0.0f64, 0.0, 0.0], 0.0, 2.0);
+        assert_eq!([x, y, theta], [2.0, 0.0, 0.0]);
+
+        // Quarter circle of radius 1 to the left
+        let [x, y, theta] = simulate_arc([0.0f64, 0.0, 0.0], 1.0, PI / 2.0);
+        assert!((x - 1.0).abs() < 1e-9 && (y - 1.0).abs() < 1e-9);
+        assert!((theta - PI / 2.0).abs() < 1e-9);
+
+        // Heading wraps into [0, 2π)
+        let [_, _, theta] = simulate_arc([0.0f64, 0.0, 0.0], -1.0, PI / 2.0);
+        assert!((theta - 1.5 * PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trace_arc_ends_at_simulated_pose() {
+        let poses = trace_arc([1.0f64, 2.0, 0.3], 0.4, 3.0, 6);
+        assert_eq!(poses.len(), 6);
+        assert_eq!(poses[5], simulate_arc([1.0, 2.0, 0.3], 0.4, 3.0));
+    }
+
+    #[test]
+    fn test_path_is_drivable() {
+        let quantizer = Quantizer::with_n([0.0, 0.0, 0.0], [20.0, 20.0, 2.0 * PI], [41, 41, 16])
+            .with_periodic([false, false, true]);
+        let primitives = primitives();
+        let goal = [10.0, 6.0];
+
+        let (path, cost) = quantized_hybrid_astar(
+            &quantizer,
+            [2.0, 2.0, 0.0],
+            &primitives,
+            4,
+            |&[x, y, _]| (0.0..=20.0).contains(&x) && (0.0..=20.0).contains(&y),
+            |&pose| (distance(pose, goal) * 10.0) as u32,
+            |&pose| distance(pose, goal) < 0.75,
+        ).unwrap();
+
+        assert_eq!(path[0], [2.0, 2.0, 0.0]);
+        assert!(distance(*path.last().unwrap(), goal) < 0.75);
+        assert_eq!(cost, 10 * (path.len() as u32 - 1));
+        // Every step is exactly one primitive, nothing is snapped to the lattice
+        for pair in path.windows(2) {
+            assert!(primitives.iter().any(|p| {
+                let end = simulate_arc(pair[0], p.curvature, p.length);
+                (0..3).all(|k| (end[k] - pair[1][k]).abs() < 1e-9)
+            }));
+        }
+    }
+
+    #[test]
+    fn test_drives_around_a_wall() {
+        let quantizer = Quantizer::with_n([0.0, 0.0, 0.0], [20.0, 20.0, 2.0 * PI], [41, 41, 16])
+            .with_periodic([false, false, true]);
+        let primitives = primitives();
+        let goal = [14.0, 4.0];
+        let wall = |x: f64, y: f64| (8.0..=9.0).contains(&x) && y <= 10.0;
+
+        let (path, _) = quantized_hybrid_astar(
+            &quantizer,
+            [4.0, 4.0, 0.0],
+            &primitives,
+            8,
+            |&[x, y, _]| (0.0..=20.0).contains(&x) && (0.0..=20.0).contains(&y) && !wall(x, y),
+            |&pose| (distance(pose, goal) * 10.0) as u32,
+            |&pose| distance(pose, goal) < 1.0,
+        ).unwrap();
+
+        assert!(path.iter().any(|&[_, y, _]| y > 10.0));
+        assert!(path.iter().all(|&[x, y, _]| !wall(x, y)));
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let quantizer = Quantizer::with_n([0.0, 0.0, 0.0], [20.0, 20.0, 2.0 * PI], [41, 41, 16])
+            .with_periodic([false, false, true]);
+        let result = quantized_hybrid_astar(
+            &quantizer,
+            [4.0, 4.0, 0.0],
+            &primitives(),
+            4,
+            |&[x, y, _]| (2.0..=6.0).contains(&x) && (2.0..=6.0).contains(&y),
+            |_| 0,
+            |&pose| distance(pose, [15.0, 15.0]) < 1.0,
+        );
+        assert!(result.is_none());
+    }
+}
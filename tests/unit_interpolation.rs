@@ -0,0 +1,104 @@
+use quantized_pathfinding::{
+    directed::*,
+    utils::interpolation::*,
+    utils::quantizer::*,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolate_1d() {
+        let values = [0.0f64, 10.0, 30.0];
+        let sample = |[i]: [usize; 1]| Some(values[i]);
+
+        assert_eq!(interpolate([0.0], [3], sample), Some(0.0));
+        assert_eq!(interpolate([0.5], [3], sample), Some(5.0));
+        assert_eq!(interpolate([1.25], [3], sample), Some(15.0));
+        assert_eq!(interpolate([2.0], [3], sample), Some(30.0));
+        // clamped at both ends
+        assert_eq!(interpolate([-3.0], [3], sample), Some(0.0));
+        assert_eq!(interpolate([7.0], [3], sample), Some(30.0));
+    }
+
+    #[test]
+    fn test_interpolate_bilinear() {
+        // f(x, y) = x + 2y is reproduced exactly
+        let sample = |[x, y]: [usize; 2]| Some(x as f64 + 2.0 * y as f64);
+        assert_eq!(interpolate([0.5, 0.5], [4, 4], sample), Some(1.5));
+        assert_eq!(interpolate([2.25, 1.75], [4, 4], sample), Some(5.75));
+    }
+
+    #[test]
+    fn test_interpolate_trilinear_vector() {
+        let sample = |[x, y, z]: [usize; 3]| Some([x as f32, y as f32, z as f32]);
+        assert_eq!(
+            interpolate_vector([0.5, 1.25, 0.75], [2, 3, 2], sample),
+            Some([0.5, 1.25, 0.75]),
+        );
+    }
+
+    #[test]
+    fn test_missing_cells_are_skipped() {
+        let sample = |[x, _]: [usize; 2]| if x == 0 { None } else { Some(4.0f32) };
+        assert_eq!(interpolate([0.5, 0.5], [2, 2], sample), Some(4.0));
+        assert_eq!(interpolate([0.0, 0.5], [2, 2], sample), None);
+    }
+
+    #[test]
+    fn test_wrapping_interpolate() {
+        let values = [0.0f64, 10.0, 20.0, 30.0];
+        let sample = |[i]: [usize; 1]| Some(values[i]);
+
+        assert_eq!(wrapping_interpolate([1.5], [4], [true], sample), Some(15.0));
+        // the last level blends into level 0
+        assert_eq!(wrapping_interpolate([3.5], [4], [true], sample), Some(15.0));
+        assert_eq!(wrapping_interpolate([-0.25], [4], [true], sample), Some(7.5));
+        assert_eq!(wrapping_interpolate([5.0], [4], [true], sample), Some(10.0));
+        assert_eq!(wrapping_interpolate([3.5], [4], [false], sample), Some(30.0));
+    }
+
+    #[test]
+    fn test_distance_field_interpolation() {
+        let quantizer = Quantizer::<f32, 1>::with_n([0.0], [4.0], [5]);
+        let field = quantized_dijkstra_field(&quantizer, [[0.0]], |&[x]| {
+            [x.checked_sub(1), Some(x + 1).filter(|&x| x < 5)]
+                .into_iter()
+                .flatten()
+                .map(|m| ([m], 2u32))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(field.sample(&quantizer, [1.4]), Some(2));
+        assert_eq!(field.interpolate(&quantizer, [1.4], |c| c as f32), Some(2.8));
+    }
+
+    #[test]
+    fn test_flow_field_interpolation() {
+        let quantizer = Quantizer::<f64, 2>::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        // A pricier cell below [3, 3] so that it heads +x without a tie
+        let flow = quantized_flow_field(&quantizer, [4.0, 2.0], |&n| {
+            if n == [3, 2] { Some(2u32) } else { Some(1) }
+        });
+
+        // Between a cell heading +x and a cell heading -y
+        let [dx, dy] = flow.interpolate(&quantizer, [3.5, 3.0]).unwrap();
+        assert!((dx - 0.5).abs() < 1e-9);
+        assert!((dy + 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_interpolation_across_periodic_seam() {
+        let quantizer = Quantizer::<f32, 1>::with_n([0.0], [8.0], [8]).with_periodic([true]);
+        let field = quantized_dijkstra_field(&quantizer, [[0.0]], |&[x]| {
+            [([(x + 7) % 8], 2u32), ([(x + 1) % 8], 2)]
+        });
+        let flow = quantized_flow_field(&quantizer, [0.0], |_| Some(1u32));
+
+        assert_eq!(field.get([7]), Some(2));
+        assert_eq!(field.interpolate(&quantizer, [7.5], |c| c as f32), Some(1.0));
+        assert_eq!(field.interpolate(&quantizer, [-0.5], |c| c as f32), Some(1.0));
+        assert_eq!(flow.interpolate(&quantizer, [7.5]), Some([0.5]));
+    }
+}
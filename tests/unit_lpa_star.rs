@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::{grid::*, quantizer::*},
+};
+
+// common for testing
+mod common;
+use common::{fresh_cost, manhattan, square_quantizer};
+
+const N: usize = 10;
+const START: [f32; 2] = [0.0, 0.0];
+const GOAL: [f32; 2] = [9.0, 9.0];
+
+type Heuristic = fn(&[usize; 2], &[usize; 2]) -> u32;
+
+fn planner() -> LpaStar<f32, u32, Quantizer<f32, 2>, Heuristic, 2> {
+    LpaStar::new(square_quantizer(N), START, GOAL, &face_offsets(), |_| Some(1), manhattan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initial_path() {
+        let mut planner = planner();
+        let (path, cost) = planner.compute_shortest_path().unwrap();
+        assert_eq!(cost, 18);
+        assert_eq!(path.len(), 19);
+        assert_eq!(path[0], START);
+        assert_eq!(path.last(), Some(&GOAL));
+        assert_eq!(planner.cost_from_start(planner.goal()), Some(18));
+    }
+
+    #[test]
+    fn test_tower_placement() {
+        let mut planner = planner();
+        planner.compute_shortest_path();
+
+        // Towers forcing a detour through the top of the map
+        planner.update_region([2.8, -0.5], [3.2, 7.2], None);
+        let (path, cost) = planner.compute_shortest_path().unwrap();
+        let blocked: HashSet<_> = box_cells([3, 0], [3, 7], [N, N]).into_iter().collect();
+        assert_eq!(Some(cost), fresh_cost(&square_quantizer(N), &blocked, START, GOAL));
+        for p in &path {
+            assert!(!blocked.contains(&square_quantizer(N).quantize(*p)));
+        }
+
+        // Sealing the map and opening it again
+        planner.update_cells([[3, 8], [3, 9]], None);
+        assert!(planner.compute_shortest_path().is_none());
+        planner.update_cells([[3, 9]], Some(1));
+        let (path, cost) = planner.compute_shortest_path().unwrap();
+        assert_eq!(cost, 18);
+        assert!(path.contains(&[3.0, 9.0]));
+    }
+
+    #[test]
+    fn test_cost_changes() {
+        let mut planner = planner();
+        planner.compute_shortest_path();
+        // Mud along the bottom and left edges, the straight paths now cost more
+        let edges = (1..N).flat_map(|k| [[k, 0], [0, k]]);
+        planner.update_cells(edges, Some(5));
+        let (path, cost) = planner.compute_shortest_path().unwrap();
+        assert_eq!(cost, 5 + 17);
+        assert!(path[1] == [1.0, 0.0] || path[1] == [0.0, 1.0]);
+        assert_eq!(path[2], [1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_start_at_goal() {
+        let mut planner = LpaStar::new(
+            square_quantizer(N), [4.0, 4.0], [4.0, 4.0], &face_offsets(), |_| Some(1u32), manhattan,
+        );
+        assert_eq!(planner.compute_shortest_path(), Some((vec![[4.0, 4.0]], 0)));
+        planner.update_cells([[4, 4]], None);
+        assert_eq!(planner.compute_shortest_path(), None);
+    }
+
+    #[test]
+    fn test_matches_fresh_search_after_many_updates() {
+        let mut planner = planner();
+        let mut blocked = HashSet::new();
+        let mut seed = 11u64;
+        for _ in 0..80 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let cell = [(seed >> 33) as usize % N, (seed >> 45) as usize % N];
+            if cell == [0, 0] || cell == [N - 1, N - 1] {
+                continue;
+            }
+            let block = blocked.insert(cell);
+            if !block {
+                blocked.remove(&cell);
+            }
+            planner.update_cells([cell], if block { None } else { Some(1) });
+            let expected = fresh_cost(&square_quantizer(N), &blocked, START, GOAL);
+            assert_eq!(planner.compute_shortest_path().map(|(_, cost)| cost), expected);
+        }
+    }
+}
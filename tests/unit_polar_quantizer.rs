@@ -0,0 +1,80 @@
+use quantized_pathfinding::{
+    traits::*,
+    utils::polar_quantizer::*,
+};
+
+// common for testing
+mod common;
+use common::close;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polar_levels() {
+        // radius 0, 1, 2 and 4 sectors of 90 degrees
+        let quantizer = PolarQuantizer::with_n([1.0f64, 1.0], 2.0, [3, 4]);
+
+        assert_eq!(quantizer.n_levels(), [3, 4]);
+        assert_eq!(quantizer.quantize([3.0, 1.0]), [2, 0]);
+        assert_eq!(quantizer.quantize([1.0, 2.0]), [1, 1]);
+        assert_eq!(quantizer.quantize([0.0, 1.0]), [1, 2]);
+        assert_eq!(quantizer.quantize([1.0, -0.9]), [2, 3]);
+
+        assert!(close(quantizer.dequantize([2, 0]), [3.0, 1.0]));
+        assert!(close(quantizer.dequantize([1, 1]), [1.0, 2.0]));
+        assert!(close(quantizer.dequantize([0, 3]), [1.0, 1.0]));
+    }
+
+    #[test]
+    fn test_polar_wraps_at_two_pi() {
+        let quantizer = PolarQuantizer::with_n([0.0f64, 0.0], 10.0, [11, 8]);
+
+        // Just below the +x axis is closer to level 0 than to level 7
+        assert_eq!(quantizer.quantize([5.0, -0.1]), [5, 0]);
+        assert_eq!(quantizer.quantize([5.0, 0.1]), [5, 0]);
+        assert_eq!(quantizer.quantize([-5.0, -0.1]), [5, 4]);
+
+        let [_, c] = quantizer.cell_coordinate([0.0, -1.0]);
+        assert!((c - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polar_radius_clamped() {
+        let quantizer = PolarQuantizer::with_n([0.0f32, 0.0], 4.0, [5, 4]);
+
+        assert_eq!(quantizer.quantize([100.0, 0.0]), [4, 0]);
+        assert_eq!(quantizer.quantize([0.0, 0.0]), [0, 0]);
+    }
+
+    #[test]
+    fn test_spherical_levels() {
+        // radius 0..2, azimuth in 90 degree steps, elevation -90, -45, 0, 45, 90
+        let quantizer = SphericalQuantizer::with_n([0.0f64, 0.0, 0.0], 2.0, [3, 4, 5]);
+
+        assert_eq!(quantizer.n_levels(), [3, 4, 5]);
+        assert_eq!(quantizer.quantize([2.0, 0.0, 0.0]), [2, 0, 2]);
+        assert_eq!(quantizer.quantize([0.0, -1.0, 0.0]), [1, 3, 2]);
+        assert_eq!(quantizer.quantize([0.0, 0.0, 2.0])[2], 4);
+        assert_eq!(quantizer.quantize([0.0, 0.0, -1.0])[2], 0);
+        assert_eq!(quantizer.quantize([1.0, 0.0, 1.0]), [1, 0, 3]);
+
+        assert!(close(quantizer.dequantize([2, 1, 2]), [0.0, 2.0, 0.0]));
+        assert!(close(quantizer.dequantize([1, 0, 4]), [0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_spherical_roundtrip() {
+        let quantizer = SphericalQuantizer::with_n([1.0f64, -2.0, 3.0], 10.0, [11, 36, 19]);
+
+        for n in [[0, 0, 9], [3, 7, 4], [10, 35, 18], [5, 18, 12]] {
+            let x = quantizer.dequantize(n);
+            let m = quantizer.quantize(x);
+            assert_eq!(m[0], n[0]);
+            if n[0] > 0 && n[2] > 0 && n[2] < 18 {
+                assert_eq!(m, n);
+            }
+        }
+    }
+}
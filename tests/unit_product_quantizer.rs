@@ -0,0 +1,83 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::breakpoint_quantizer::*,
+    utils::companding_quantizer::*,
+    utils::grid::*,
+    utils::product_quantizer::*,
+    utils::quantizer::*,
+};
+
+// (x, y, heading) with heading in 90 degree steps
+fn pose_quantizer() -> ProductQuantizer<f64, 3> {
+    ProductQuantizer::new([
+        Box::new(Quantizer::with_n([0.0], [4.0], [5])),
+        Box::new(BreakpointQuantizer::with_edges([vec![0.0, 1.0, 2.0, 4.0]])),
+        Box::new(Quantizer::with_n([0.0], [360.0], [4]).with_periodic([true])),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixed_axes() {
+        let quantizer = pose_quantizer();
+
+        assert_eq!(quantizer.n_levels(), [5, 3, 4]);
+        assert_eq!(quantizer.periodic(), [false, false, true]);
+
+        assert_eq!(quantizer.quantize([2.2, 3.5, 100.0]), [2, 2, 1]);
+        assert_eq!(quantizer.quantize([0.0, 0.2, -80.0]), [0, 0, 3]);
+        assert_eq!(quantizer.quantize([4.0, 1.5, 350.0]), [4, 1, 0]);
+        assert_eq!(quantizer.dequantize([1, 2, 3]), [1.0, 3.0, 270.0]);
+        assert_eq!(quantizer.cell_coordinate([1.5, 1.5, 45.0]), [1.5, 1.0, 0.5]);
+        assert_eq!(quantizer.axis(2).n_levels(), [4]);
+    }
+
+    #[test]
+    fn test_clone_and_debug() {
+        let quantizer = pose_quantizer();
+        let copy = quantizer.clone();
+
+        assert_eq!(copy.quantize([2.2, 3.5, 100.0]), quantizer.quantize([2.2, 3.5, 100.0]));
+        assert_eq!(copy.periodic(), [false, false, true]);
+        assert!(format!("{:?}", copy).contains("BreakpointQuantizer"));
+    }
+
+    #[test]
+    fn test_log_axis() {
+        let quantizer: ProductQuantizer<f32, 2> = ProductQuantizer::new([
+            Box::new(Quantizer::with_n([0.0], [10.0], [11])),
+            Box::new(LogQuantizer::with_n([0.0], [1023.0], [1.0], [11])),
+        ]);
+
+        assert_eq!(quantizer.quantize([3.0, 6.0]), [3, 3]);
+        assert_eq!(quantizer.quantize_ieee754([3.6, 6.0]), [3, 2]);
+    }
+
+    #[test]
+    fn test_turning_in_place_across_the_seam() {
+        let quantizer = pose_quantizer();
+        let goal_n = quantizer.quantize([0.0, 0.5, 270.0]);
+        let offsets = face_offsets::<3>();
+
+        let (path, cost) = quantized_astar(
+            &quantizer,
+            [0.0, 0.5, 0.0],
+            |&n| {
+                grid_neighbors(&quantizer, n, &offsets)
+                    .into_iter()
+                    .map(|m| (m, 1u32))
+                    .collect::<Vec<_>>()
+            },
+            |_| 0,
+            |&n| n == goal_n,
+        ).unwrap();
+
+        // 0 -> 270 degrees is a single turn through the periodic axis
+        assert_eq!(cost, 1);
+        assert_eq!(path.last(), Some(&[0.0, 0.5, 270.0]));
+    }
+}
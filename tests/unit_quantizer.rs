@@ -1,3 +1,6 @@
+// Lints in the existing tests, kept as written
+#![allow(unused_imports, clippy::clone_on_copy)]
+
 use std::ops::{Add, Sub, Mul, Div};
 use std::array::from_fn;
 use quantized_pathfinding::{
@@ -496,4 +499,182 @@ mod tests {
         // Each should quantize based on their respective ranges
         assert_eq!(quantized, [1, 1, 1]); // Middle points in each dimension
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_cell_coordinate() {
+        let a = [-1.0f32, 0.0f32];
+        let b = [1.0f32, 10.0f32];
+        let n_levels = [3, 11];
+
+        let quantizer = Quantizer::with_n(a, b, n_levels);
+
+        assert_eq!(quantizer.cell_coordinate([-1.0, 0.0]), [0.0, 0.0]);
+        assert_eq!(quantizer.cell_coordinate([0.5, 2.5]), [1.5, 2.5]);
+        assert_eq!(quantizer.cell_coordinate([1.0, 10.0]), [2.0, 10.0]);
+
+        // Caller decides the tie-breaking at the half step
+        let [c] = Quantizer::with_n([0.0f64], [10.0f64], [11]).cell_coordinate([2.5]);
+        assert_eq!(c, 2.5);
+        assert_eq!(c.round().to_usize(), 3);
+        assert_eq!(c.round_ieee754(), 2);
+    }
+
+    #[test]
+    fn test_residual() {
+        let a = [0.0f64, 0.0f64];
+        let b = [10.0f64, 20.0f64];
+        let step_size = [2.0f64, 5.0f64];
+
+        let quantizer = Quantizer::with_step_size(a, b, step_size);
+
+        assert_eq!(quantizer.residual([4.0, 10.0]), [0.0, 0.0]);
+        assert_eq!(quantizer.residual([4.5, 11.0]), [0.5, 1.0]);
+        assert_eq!(quantizer.residual([5.5, 13.0]), [-0.5, -2.0]);
+    }
+
+    #[test]
+    fn test_residual_pseudo_float() {
+        let a = [PseudoFloat::new(0.0)];
+        let b = [PseudoFloat::new(1.0)];
+        let n_levels = [5];
+
+        let quantizer = Quantizer::with_n(a, b, n_levels);
+
+        assert_eq!(quantizer.cell_coordinate([PseudoFloat::new(0.375)]), [PseudoFloat::new(1.5)]);
+        let [r] = quantizer.residual([PseudoFloat::new(0.3)]);
+        assert!((r.value() - 0.05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cell_centered_with_n() {
+        let a = [0.0f32, 0.0f32];
+        let b = [10.0f32, 4.0f32];
+        let n_levels = [5, 4]; // tiles of 2.0 x 1.0
+
+        let quantizer = Quantizer::with_n_aligned(a, b, n_levels, Alignment::CellCentered);
+
+        assert_eq!(quantizer.n_levels, [5, 4]);
+        assert_eq!(quantizer.step_size(), [2.0, 1.0]);
+        assert_eq!(quantizer.alignment(), Alignment::CellCentered);
+
+        // Every tile has the same width, boundaries included
+        assert_eq!(quantizer.quantize([0.0, 0.0]), [0, 0]);
+        assert_eq!(quantizer.quantize([1.9, 0.9]), [0, 0]);
+        assert_eq!(quantizer.quantize([2.0, 1.0]), [1, 1]);
+        assert_eq!(quantizer.quantize([9.9, 3.9]), [4, 3]);
+
+        // Levels are tile centers
+        assert_eq!(quantizer.dequantize([0, 0]), [1.0, 0.5]);
+        assert_eq!(quantizer.dequantize([4, 3]), [9.0, 3.5]);
+        assert_eq!(quantizer.cell_coordinate([1.0, 0.5]), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_cell_centered_quantize_ieee754() {
+        let quantizer = Quantizer::with_n_aligned([0.0f32], [10.0f32], [5], Alignment::CellCentered);
+
+        // Truncated to the tile holding x, not towards the previous center
+        assert_eq!(quantizer.quantize_ieee754([0.0]), [0]);
+        assert_eq!(quantizer.quantize_ieee754([2.5]), [1]);
+        assert_eq!(quantizer.quantize_ieee754([3.9]), [1]);
+        assert_eq!(quantizer.quantize_ieee754([9.9]), [4]);
+    }
+
+    #[test]
+    fn test_cell_centered_with_step_size() {
+        let a = [-4.0f64];
+        let b = [4.0f64];
+        let step_size = [2.0f64];
+
+        let quantizer = Quantizer::with_step_size_aligned(a, b, step_size, Alignment::CellCentered);
+
+        // 4 tiles instead of the 5 vertex-aligned levels
+        assert_eq!(quantizer.n_levels, [4]);
+        assert_eq!(Quantizer::with_step_size(a, b, step_size).n_levels, [5]);
+
+        assert_eq!(quantizer.quantize([-3.0]), [0]);
+        assert_eq!(quantizer.quantize([-0.5]), [1]);
+        assert_eq!(quantizer.quantize([0.5]), [2]);
+        assert_eq!(quantizer.dequantize([3]), [3.0]);
+    }
+
+    #[test]
+    fn test_vertex_alignment_is_default() {
+        let quantizer = Quantizer::with_n([0.0f32], [10.0f32], [11]);
+        let aligned = Quantizer::with_n_aligned([0.0f32], [10.0f32], [11], Alignment::default());
+
+        assert_eq!(quantizer.alignment(), Alignment::Vertex);
+        assert_eq!(aligned.alignment(), Alignment::Vertex);
+        assert_eq!(quantizer.dequantize([3]), aligned.dequantize([3]));
+        assert_eq!(quantizer.quantize([2.5]), aligned.quantize([2.5]));
+    }
+
+    #[test]
+    fn test_periodic_axis() {
+        let a = [0.0f32, 0.0f32];
+        let b = [10.0f32, 360.0f32];
+        let n_levels = [11, 8]; // heading in 45 degree steps
+
+        let quantizer = Quantizer::with_n(a, b, n_levels).with_periodic([false, true]);
+
+        assert_eq!(quantizer.periodic(), [false, true]);
+        assert_eq!(quantizer.step_size(), [1.0, 45.0]);
+
+        assert_eq!(quantizer.quantize([5.0, 90.0]), [5, 2]);
+        assert_eq!(quantizer.quantize([5.0, 350.0]), [5, 0]); // closer to 360 = 0
+        assert_eq!(quantizer.quantize([5.0, 360.0]), [5, 0]);
+        assert_eq!(quantizer.quantize([5.0, -45.0]), [5, 7]);
+        assert_eq!(quantizer.quantize([5.0, 405.0]), [5, 1]);
+        assert_eq!(quantizer.quantize([5.0, -720.0]), [5, 0]);
+        assert_eq!(quantizer.quantize_ieee754([5.0, -10.0]), [5, 7]);
+
+        assert_eq!(quantizer.dequantize([0, 7]), [0.0, 315.0]);
+        assert_eq!(quantizer.dequantize([0, 9]), [0.0, 45.0]);
+        assert_eq!(quantizer.cell_coordinate([0.0, -90.0]), [0.0, 6.0]);
+    }
+
+    #[test]
+    fn test_periodic_keeps_step_size() {
+        let quantizer = Quantizer::with_step_size([0.0f64], [360.0f64], [45.0f64])
+            .with_periodic([true]);
+
+        assert_eq!(quantizer.step_size(), [45.0]);
+        assert_eq!(quantizer.n_levels, [8]);
+        assert_eq!(quantizer.dequantize([2]), [90.0]);
+        assert_eq!(quantizer.quantize([350.0]), [0]);
+    }
+
+    #[test]
+    fn test_periodic_cell_centered() {
+        let quantizer = Quantizer::with_n_aligned([0.0f64], [4.0f64], [4], Alignment::CellCentered)
+            .with_periodic([true]);
+
+        assert_eq!(quantizer.step_size(), [1.0]);
+        assert_eq!(quantizer.quantize([0.0]), [0]);
+        assert_eq!(quantizer.quantize([3.9]), [3]);
+        assert_eq!(quantizer.quantize([4.1]), [0]);
+        assert_eq!(quantizer.quantize([-0.1]), [3]);
+        assert_eq!(quantizer.dequantize([3]), [3.5]);
+    }
+
+    #[test]
+    fn test_periodic_residual() {
+        let quantizer = Quantizer::with_n([0.0f32], [360.0f32], [8])
+            .with_periodic([true]);
+
+        assert_eq!(quantizer.residual([359.0]), [-1.0]);
+        assert_eq!(quantizer.residual([370.0]), [10.0]);
+        assert_eq!(quantizer.residual([-10.0]), [-10.0]);
+        assert_eq!(quantizer.wrapped_difference([350.0], [10.0]), [20.0]);
+        assert_eq!(quantizer.wrapped_difference([10.0], [350.0]), [-20.0]);
+    }
+
+    #[test]
+    fn test_periodic_pseudo_float() {
+        let quantizer = Quantizer::with_n([PseudoFloat::new(0.0)], [PseudoFloat::new(1.0)], [4])
+            .with_periodic([true]);
+
+        assert_eq!(quantizer.quantize([PseudoFloat::new(1.3)]), [1]);
+        assert_eq!(quantizer.quantize([PseudoFloat::new(-0.25)]), [3]);
+    }
+}
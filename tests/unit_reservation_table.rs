@@ -0,0 +1,123 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::{quantizer::*, reservation_table::*},
+};
+
+// common for testing
+mod common;
+use common::{assert_conflict_free, manhattan, successors};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_quantizer_agrees_on_occupancy() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        let mut table = ReservationTable::new();
+        table.reserve_position(&quantizer, [2.02, 3.0], 4);
+        // A less precise agent still sees the same cell
+        assert!(table.is_position_reserved(&quantizer, [1.97, 2.96], 4));
+        assert!(!table.is_position_reserved(&quantizer, [1.97, 2.96], 5));
+        assert!(table.is_reserved([2, 3], 4));
+        assert!(!table.is_reserved([2, 2], 4));
+    }
+
+    #[test]
+    fn test_moves_and_parking() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        let mut table = ReservationTable::new();
+        table.reserve_path(&[([0, 0], 0), ([1, 0], 1), ([1, 1], 2)]);
+        assert!(table.is_reserved([1, 0], 1));
+        // Head-on through a reserved move
+        assert!(table.is_move_blocked([1, 0], [0, 0], 1));
+        assert!(!table.is_move_blocked([2, 0], [2, 1], 1));
+        // Resting at the end of the path
+        assert!(table.is_reserved([1, 1], 2));
+        assert!(table.is_reserved([1, 1], 100));
+        assert!(!table.is_reserved([1, 1], 1));
+
+        table.park_position(&quantizer, [3.1, 3.2], 7);
+        assert!(table.is_reserved([3, 3], 9));
+        table.clear();
+        assert!(!table.is_reserved([3, 3], 9));
+        assert!(!table.is_reserved([1, 1], 100));
+    }
+
+    #[test]
+    fn test_prioritized_fleet() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        let mut table = ReservationTable::new();
+        // Crossing the middle, no goal on a start that is held until planned
+        let agents = [
+            ([0.0, 2.0], [4.0, 3.0]),
+            ([4.0, 2.0], [0.0, 1.0]),
+            ([2.0, 0.0], [1.0, 4.0]),
+            ([2.0, 4.0], [3.0, 0.0]),
+        ];
+        let results = quantized_prioritized_planning(
+            &quantizer, &mut table, &agents, 30, 1, successors(quantizer, |_| true), manhattan,
+        );
+        let paths: Vec<_> = results.iter().map(|r| &r.as_ref().unwrap().0).collect();
+        assert_conflict_free(&paths);
+        // The first agent goes straight
+        assert_eq!(results[0].as_ref().unwrap().1, 5);
+        for (path, (start, goal)) in paths.iter().zip(agents) {
+            assert_eq!(path[0], (0, start));
+            assert_eq!(path.last().unwrap().1, goal);
+        }
+    }
+
+    #[test]
+    fn test_respects_earlier_reservations() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        let mut table = ReservationTable::new();
+        // A robot planned on an earlier tick, reported with noisy coordinates
+        let earlier: TimedPath<f32, 2> = (0..5).map(|t| (t, [t as f32 + 0.03, 1.98])).collect();
+        table.reserve_timed_path(&quantizer, &earlier);
+
+        let agents = [([4.0, 2.0], [0.0, 2.0])];
+        let results = quantized_prioritized_planning(
+            &quantizer, &mut table, &agents, 30, 1, successors(quantizer, |_| true), manhattan,
+        );
+        let (path, cost) = results[0].as_ref().unwrap();
+        let snapped: TimedPath<f32, 2> = earlier.iter()
+            .map(|&(t, x)| (t, quantizer.dequantize(quantizer.quantize(x))))
+            .collect();
+        assert_conflict_free(&[&snapped, path]);
+        assert!(*cost > 4);
+    }
+
+    #[test]
+    fn test_priority_order_matters() {
+        // In a corridor with a pocket the first agent parks in the way of the second
+        let quantizer = Quantizer::with_n([0.0, 0.0], [4.0, 1.0], [5, 2]);
+        let free = |&[x, y]: &[usize; 2]| y == 0 || x == 2;
+        let agents = [([0.0, 0.0], [3.0, 0.0]), ([4.0, 0.0], [1.0, 0.0])];
+        let mut table = ReservationTable::new();
+        let results = quantized_prioritized_planning(
+            &quantizer, &mut table, &agents, 20, 1, successors(quantizer, free), manhattan,
+        );
+        assert!(results[0].is_some());
+        assert!(results[1].is_none());
+        // The stuck agent keeps its start
+        assert!(table.is_reserved([4, 0], 50));
+    }
+
+    #[test]
+    fn test_waiting_agents_hold_their_starts() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [4.0, 4.0], [5, 5]);
+        // The second agent stays where it is, right on the first agent's route
+        let mut table = ReservationTable::new();
+        let agents = [([0.0, 2.0], [4.0, 2.0]), ([2.0, 2.0], [2.0, 2.0])];
+        let results = quantized_prioritized_planning(
+            &quantizer, &mut table, &agents, 30, 1, successors(quantizer, |_| true), manhattan,
+        );
+        let paths: Vec<_> = results.iter().map(|r| &r.as_ref().unwrap().0).collect();
+        assert_conflict_free(&paths);
+        assert!(paths[0].iter().all(|&(_, x)| x != [2.0, 2.0]));
+        assert_eq!(results[0].as_ref().unwrap().1, 6);
+        assert_eq!(results[1], Some((vec![(0, [2.0, 2.0])], 0)));
+    }
+}
@@ -0,0 +1,140 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::quantizer::*,
+};
+
+// common for testing
+mod common;
+use common::successors;
+
+fn manhattan(goal: [usize; 2]) -> impl FnMut(&[usize; 2]) -> u32 {
+    move |n| (n[0].abs_diff(goal[0]) + n[1].abs_diff(goal[1])) as u32
+}
+
+fn plan(
+    quantizer: &Quantizer<f32, 2>,
+    obstacles: &[MovingRegion<f32, 2>],
+    horizon: usize,
+) -> Option<(TimedPath<f32, 2>, u32)> {
+    let goal = [9, 1];
+    quantized_space_time_astar(
+        quantizer, [0.0, 1.0], obstacles, horizon, 1,
+        successors(*quantizer, |_| true), manhattan(goal), |n| *n == goal,
+    )
+}
+
+// No waypoint lies in an obstacle at its time step
+fn assert_clear(quantizer: &Quantizer<f32, 2>, path: &TimedPath<f32, 2>, obstacles: &[MovingRegion<f32, 2>]) {
+    for &(t, x) in path {
+        let n = quantizer.quantize(x);
+        for obstacle in obstacles {
+            assert!(!obstacle.cells(quantizer, t).contains(&n), "hit at t={t} {x:?}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moving_region() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [9.0, 2.0], [10, 3]);
+        let region = MovingRegion::new([1.0, 0.0], [2.0, 0.0])
+            .with_velocity([1.0, 0.5])
+            .with_interval(2, 5);
+        assert_eq!(region.at(1), None);
+        assert_eq!(region.at(2), Some(([1.0, 0.0], [2.0, 0.0])));
+        assert_eq!(region.at(4), Some(([3.0, 1.0], [4.0, 1.0])));
+        assert_eq!(region.at(6), None);
+        assert_eq!(region.cells(&quantizer, 4), vec![[3, 1], [4, 1]]);
+        assert!(region.cells(&quantizer, 0).is_empty());
+    }
+
+    #[test]
+    fn test_region_leaving_the_grid() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [9.0, 2.0], [10, 3]);
+        // Already past the low side and moving further out
+        let gone = MovingRegion::new([-3.0, 0.0], [-2.0, 2.0]).with_velocity([-1.0, 0.0]);
+        assert!(gone.cells(&quantizer, 0).is_empty());
+        assert!(gone.cells(&quantizer, 3).is_empty());
+        let (path, cost) = plan(&quantizer, &[gone], 30).unwrap();
+        assert_eq!(cost, 9);
+        assert_eq!(path[0], (0, [0.0, 1.0]));
+
+        // Half a cell still overlaps level 0, past it nothing does
+        let leaving = MovingRegion::new([0.2, 0.0], [0.2, 2.0]).with_velocity([-0.5, 0.0]);
+        assert_eq!(leaving.cells(&quantizer, 1), vec![[0, 0], [0, 1], [0, 2]]);
+        assert!(leaving.cells(&quantizer, 2).is_empty());
+
+        // Off the high side
+        let beyond = MovingRegion::new([9.6, 0.0], [12.0, 2.0]);
+        assert!(beyond.cells(&quantizer, 0).is_empty());
+    }
+
+    #[test]
+    fn test_region_across_periodic_seam() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [8.0, 2.0], [8, 3])
+            .with_periodic([true, false]);
+        let region = MovingRegion::new([6.6, 0.0], [7.4, 0.0]).with_velocity([0.5, 0.0]);
+
+        assert_eq!(region.cells(&quantizer, 0), vec![[7, 0]]);
+        assert_eq!(region.cells(&quantizer, 1), vec![[7, 0], [0, 0]]);
+        assert_eq!(region.cells(&quantizer, 3), vec![[0, 0], [1, 0]]);
+    }
+
+    #[test]
+    fn test_free_aisle() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [9.0, 2.0], [10, 3]);
+        let (path, cost) = plan(&quantizer, &[], 30).unwrap();
+        assert_eq!(cost, 9);
+        assert_eq!(path.len(), 10);
+        assert_eq!(path[0], (0, [0.0, 1.0]));
+        assert_eq!(path.last(), Some(&(9, [9.0, 1.0])));
+        assert!(path.windows(2).all(|w| w[1].0 == w[0].0 + 1));
+    }
+
+    #[test]
+    fn test_waits_for_crossing_traffic() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [9.0, 2.0], [10, 3]);
+        // Another forklift blocks the whole width at x = 5 during t = 3..=8
+        let crossing = [MovingRegion::new([5.0, 0.0], [5.0, 2.0]).with_interval(3, 8)];
+        let (path, cost) = plan(&quantizer, &crossing, 30).unwrap();
+        assert_clear(&quantizer, &path, &crossing);
+        assert_eq!(path.last().unwrap().0, 13);
+        assert_eq!(cost, 13);
+        // Waiting shows up as a repeated position
+        assert!(path.windows(2).any(|w| w[0].1 == w[1].1));
+    }
+
+    #[test]
+    fn test_dodges_sweeping_obstacle() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [9.0, 2.0], [10, 3]);
+        // A box sweeping down the aisle towards the agent, one cell per step
+        let sweeper = [MovingRegion::new([9.0, 1.0], [9.0, 1.0]).with_velocity([-1.0, 0.0])];
+        let (path, cost) = plan(&quantizer, &sweeper, 30).unwrap();
+        assert_clear(&quantizer, &path, &sweeper);
+        assert_eq!(cost, 11);
+        assert!(path.iter().any(|(_, x)| x[1] != 1.0));
+    }
+
+    #[test]
+    fn test_goal_must_stay_free() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [9.0, 2.0], [10, 3]);
+        // The goal is taken at t = 12, arriving earlier does not count
+        let late = [MovingRegion::new([9.0, 1.0], [9.0, 1.0]).with_interval(12, 12)];
+        let (path, _) = plan(&quantizer, &late, 30).unwrap();
+        assert_clear(&quantizer, &path, &late);
+        assert!(path.last().unwrap().0 > 12);
+    }
+
+    #[test]
+    fn test_horizon() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [9.0, 2.0], [10, 3]);
+        assert!(plan(&quantizer, &[], 8).is_none());
+        assert!(plan(&quantizer, &[], 9).is_some());
+        let wall = [MovingRegion::new([5.0, 0.0], [5.0, 2.0])];
+        assert!(plan(&quantizer, &wall, 50).is_none());
+    }
+}
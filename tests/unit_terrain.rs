@@ -0,0 +1,113 @@
+use quantized_pathfinding::{
+    directed::*,
+    utils::{grid::*, quantizer::*, terrain::*},
+};
+
+// A ridge along x = 5 rising to 4 units, with a low pass at y = 9
+fn ridge(&[x, y]: &[f32; 2]) -> f32 {
+    if x == 5.0 && y != 9.0 { 4.0 } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_terrain_costs() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [10.0, 10.0], [11, 11]);
+        let map = Heightmap::from_fn(quantizer, |_| 2.0);
+        let (path, cost) = quantized_terrain_astar(
+            &map, [0.0, 0.0], [10.0, 0.0], &face_offsets(),
+        ).unwrap();
+        assert_eq!(cost, 10_000);
+        assert_eq!(path.len(), 11);
+        assert!(path.iter().all(|p| p[2] == 2.0));
+        assert_eq!(path.last(), Some(&[10.0, 0.0, 2.0]));
+    }
+
+    #[test]
+    fn test_step_cost() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [10.0, 10.0], [11, 11]);
+        let map = Heightmap::from_fn(quantizer, |&[x, _]| x * 0.5)
+            .with_climb_weight(2.0)
+            .with_descent_weight(1.0);
+        assert_eq!(map.step_cost([0, 0], [1, 0]), Some(2.0));
+        assert_eq!(map.step_cost([1, 0], [0, 0]), Some(1.5));
+        assert_eq!(map.step_cost([0, 0], [0, 1]), Some(1.0));
+        assert_eq!(map.step_cost([0, 0], [11, 0]), None);
+
+        let successors = map.successors(&[0, 0], &neighborhood_offsets(2));
+        assert!(successors.contains(&([1, 1], 2415)));
+    }
+
+    #[test]
+    fn test_periodic_terrain() {
+        let quantizer = Quantizer::with_n([0.0f32, 0.0], [8.0, 2.0], [8, 3])
+            .with_periodic([true, false]);
+        let map = Heightmap::from_fn(quantizer, |&[x, _]| x);
+
+        assert_eq!(map.step_cost([7, 0], [0, 0]), Some(1.0));
+        assert_eq!(map.heuristic([1, 0], [7, 0]), 2000);
+        assert_eq!(map.elevation([7.5, 0.0]), Some(3.5));
+
+        let flat = Heightmap::from_fn(quantizer, |_| 0.0);
+        let (path, cost) = quantized_terrain_astar(
+            &flat, [1.0, 0.0], [7.0, 0.0], &face_offsets(),
+        ).unwrap();
+        assert_eq!(cost, 2000);
+        assert_eq!(path, vec![[1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [7.0, 0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_max_slope_cutoff() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [10.0, 10.0], [11, 11]);
+        let steep = Heightmap::from_fn(quantizer, ridge).with_max_slope(1.0);
+        assert_eq!(steep.step_cost([4, 0], [5, 0]), None);
+        assert_eq!(steep.step_cost([4, 9], [5, 9]), Some(1.0));
+
+        // The only way over the ridge is through the pass
+        let (path, _) = quantized_terrain_astar(
+            &steep, [0.0, 0.0], [10.0, 0.0], &face_offsets(),
+        ).unwrap();
+        assert!(path.contains(&[5.0, 9.0, 0.0]));
+        assert!(path.iter().all(|p| p[2] == 0.0));
+
+        // Without the cutoff the ridge is climbed directly
+        let flat_cost = Heightmap::from_fn(quantizer, ridge);
+        let (path, cost) = quantized_terrain_astar(
+            &flat_cost, [0.0, 0.0], [10.0, 0.0], &face_offsets(),
+        ).unwrap();
+        assert_eq!(cost, 10_000);
+        assert!(path.contains(&[5.0, 0.0, 4.0]));
+    }
+
+    #[test]
+    fn test_climb_weight_prefers_detour() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [10.0, 10.0], [11, 11]);
+        let map = Heightmap::from_fn(quantizer, ridge).with_climb_weight(10.0);
+        let (path, cost) = quantized_terrain_astar(
+            &map, [0.0, 0.0], [10.0, 0.0], &face_offsets(),
+        ).unwrap();
+        assert!(path.contains(&[5.0, 9.0, 0.0]));
+        assert_eq!(cost, 28_000);
+    }
+
+    #[test]
+    fn test_elevation_and_waypoints() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [10.0, 10.0], [11, 11]);
+        let map = Heightmap::from_fn(quantizer, |&[x, y]| x + 2.0 * y);
+        assert_eq!(map.height([3, 1]), Some(5.0));
+        assert_eq!(map.height([11, 0]), None);
+        assert_eq!(map.waypoint([3, 1]), Some([3.0, 1.0, 5.0]));
+        assert!((map.elevation([2.5, 1.5]).unwrap() - 5.5).abs() < 1e-5);
+        assert_eq!(map.lift(&[[1.0, 1.0]]), vec![[1.0, 1.0, 3.0]]);
+        assert_eq!(map.heuristic([0, 0], [3, 4]), 5000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_wrong_height_count() {
+        let quantizer = Quantizer::<f32, 2>::with_n([0.0, 0.0], [10.0, 10.0], [11, 11]);
+        Heightmap::new(quantizer, vec![0.0; 10]);
+    }
+}
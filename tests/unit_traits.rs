@@ -1,3 +1,6 @@
+// Lints in the existing tests, kept as written
+#![allow(unused_imports, clippy::items_after_test_module, clippy::approx_constant)]
+
 use std::ops::{Add, Sub, Mul, Div};
 use quantized_pathfinding::traits::*;
 
@@ -0,0 +1,103 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::{quantizer::*, voxel::*},
+};
+
+fn plan(map: &VoxelMap<f32>, start: [f32; 3], goal: [f32; 3], connectivity: Connectivity)
+    -> Option<(Vec<[f32; 3]>, u32)>
+{
+    let goal_n = map.quantizer().quantize(goal);
+    quantized_astar(
+        map.quantizer(),
+        start,
+        |n| map.successors(n, connectivity),
+        |n| connectivity.distance(*n, goal_n),
+        |n| *n == goal_n,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connectivity_offsets() {
+        assert_eq!(Connectivity::Six.offsets().len(), 6);
+        assert_eq!(Connectivity::Eighteen.offsets().len(), 18);
+        assert_eq!(Connectivity::TwentySix.offsets().len(), 26);
+        assert_eq!(step_cost([1, 0, 0]), STRAIGHT_COST);
+        assert_eq!(step_cost([1, -1, 0]), DIAGONAL_COST);
+        assert_eq!(step_cost([-1, 1, 1]), CORNER_COST);
+    }
+
+    #[test]
+    fn test_open_space_costs() {
+        let quantizer = Quantizer::<f32, 3>::with_n([0.0; 3], [9.0; 3], [10; 3]);
+        let map = VoxelMap::new(quantizer);
+        let (path, cost) = plan(&map, [0.0; 3], [9.0; 3], Connectivity::TwentySix).unwrap();
+        assert_eq!(cost, 9 * CORNER_COST);
+        assert_eq!(path.len(), 10);
+
+        let (_, cost) = plan(&map, [0.0; 3], [9.0, 9.0, 0.0], Connectivity::Eighteen).unwrap();
+        assert_eq!(cost, 9 * DIAGONAL_COST);
+        let (_, cost) = plan(&map, [0.0; 3], [3.0, 2.0, 1.0], Connectivity::Six).unwrap();
+        assert_eq!(cost, 6 * STRAIGHT_COST);
+    }
+
+    #[test]
+    fn test_distance_is_exact_on_empty_map() {
+        let quantizer = Quantizer::<f32, 3>::with_n([0.0; 3], [9.0; 3], [10; 3]);
+        let map = VoxelMap::new(quantizer);
+        for connectivity in [Connectivity::Six, Connectivity::Eighteen, Connectivity::TwentySix] {
+            for goal in [[9.0, 4.0, 1.0], [3.0, 3.0, 3.0], [2.0, 7.0, 5.0]] {
+                let (_, cost) = plan(&map, [0.0; 3], goal, connectivity).unwrap();
+                let goal_n = map.quantizer().quantize(goal);
+                assert_eq!(cost, connectivity.distance([0; 3], goal_n));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fill_box_and_contains() {
+        let quantizer = Quantizer::<f32, 3>::with_n([0.0; 3], [9.0; 3], [10; 3]);
+        let mut map = VoxelMap::new(quantizer);
+        map.fill_box([2.0, 2.0, 2.0], [3.0, 4.0, 2.2], true);
+        assert!(map.is_occupied([2, 4, 2]));
+        assert!(map.is_free([2, 5, 2]));
+        assert!(map.contains([3.1, 3.9, 1.8]));
+        assert!(!map.contains([3.1, 3.9, 2.6]));
+        // Off the grid
+        assert!(map.is_occupied([10, 0, 0]));
+
+        map.set_occupied([2, 4, 2], false);
+        assert!(map.is_free([2, 4, 2]));
+    }
+
+    #[test]
+    fn test_no_corner_cutting() {
+        let quantizer = Quantizer::<f32, 3>::with_n([0.0; 3], [9.0; 3], [10; 3]);
+        let mut map = VoxelMap::new(quantizer);
+        map.set_occupied([1, 0, 0], true);
+        let neighbors = map.neighbors(&[0, 0, 0], Connectivity::TwentySix);
+        assert_eq!(neighbors.len(), 3);
+        assert!(!neighbors.contains(&[1, 1, 0]));
+        assert!(!neighbors.contains(&[1, 1, 1]));
+        assert!(neighbors.contains(&[0, 1, 1]));
+    }
+
+    #[test]
+    fn test_wall_with_hole() {
+        let quantizer = Quantizer::<f32, 3>::with_n([0.0; 3], [9.0; 3], [10; 3]);
+        // Wall at x = 5 with a single hole at (5, 8, 8)
+        let map = VoxelMap::from_fn(quantizer, |&[x, y, z]| {
+            x == 5.0 && !(y == 8.0 && z == 8.0)
+        });
+        let (path, cost) = plan(&map, [0.0; 3], [9.0, 0.0, 0.0], Connectivity::TwentySix).unwrap();
+        assert!(path.contains(&[5.0, 8.0, 8.0]));
+        assert!(cost > 9 * STRAIGHT_COST);
+        for p in &path {
+            assert!(!map.contains(*p));
+        }
+    }
+}
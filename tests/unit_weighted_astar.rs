@@ -0,0 +1,102 @@
+use quantized_pathfinding::{
+    directed::*,
+    traits::*,
+    utils::grid::*,
+};
+
+// common for testing
+mod common;
+use common::{
+    walls_free, walls_octile, walls_optimal, walls_quantizer, walls_successors, WALLS_GOAL, WALLS_N,
+};
+
+fn check_path(path: &[[f32; 2]]) {
+    assert_eq!(path[0], [0.0, 0.0]);
+    assert_eq!(path.last(), Some(&[29.0, 29.0]));
+    assert!(path.iter().all(|p| walls_free(&walls_quantizer().quantize(*p))));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_within_bound() {
+        let optimal = walls_optimal();
+        for epsilon in [0.25, 0.5, 1.0, 4.0] {
+            let (path, cost) = quantized_weighted_astar(
+                &walls_quantizer(), [0.0, 0.0], epsilon, walls_successors, walls_octile, |n| *n == WALLS_GOAL, |c| c as f32,
+            ).unwrap();
+            check_path(&path);
+            assert!(cost as f32 <= (1.0 + epsilon) * optimal as f32);
+        }
+    }
+
+    #[test]
+    fn test_weighted_zero_epsilon_is_optimal() {
+        let (_, cost) = quantized_weighted_astar(
+            &walls_quantizer(), [0.0, 0.0], 0.0, walls_successors, walls_octile, |n| *n == WALLS_GOAL, |c| c as f32,
+        ).unwrap();
+        assert_eq!(cost, walls_optimal());
+    }
+
+    #[test]
+    fn test_focal_within_bound() {
+        let optimal = walls_optimal();
+        for epsilon in [0.0, 0.1, 0.5, 2.0] {
+            let (path, cost) = quantized_focal_astar(
+                &walls_quantizer(), [0.0, 0.0], epsilon, walls_successors, walls_octile, walls_octile,
+                |n| *n == WALLS_GOAL, |c| c as f32,
+            ).unwrap();
+            check_path(&path);
+            assert!(cost as f32 <= (1.0 + epsilon) * optimal as f32);
+        }
+    }
+
+    #[test]
+    fn test_focal_inconsistent_heuristic() {
+        // Admissible but dropping to 0 on odd columns, so f_min goes up and down
+        let patchy = |n: &[usize; 2]| if n[0].is_multiple_of(2) { walls_octile(n) } else { 0 };
+        let optimal = walls_optimal();
+        for epsilon in [0.0, 0.2, 1.0] {
+            let (path, cost) = quantized_focal_astar(
+                &walls_quantizer(), [0.0, 0.0], epsilon, walls_successors, patchy, walls_octile,
+                |n| *n == WALLS_GOAL, |c| c as f32,
+            ).unwrap();
+            check_path(&path);
+            assert!(cost as f32 <= (1.0 + epsilon) * optimal as f32);
+        }
+    }
+
+    #[test]
+    fn test_focal_prefers_secondary_heuristic() {
+        // On an open grid every focal choice is free, avoiding the row y = 0
+        // costs nothing extra within the bound
+        let open = |n: &[usize; 2]| {
+            neighborhood_offsets::<2>(1).into_iter()
+                .filter_map(|offset| offset_cell(*n, offset, [WALLS_N, WALLS_N]))
+                .map(|m| (m, 1u32))
+                .collect::<Vec<_>>()
+        };
+        let goal = [5, 0];
+        let manhattan = |n: &[usize; 2]| (n[0].abs_diff(goal[0]) + n[1].abs_diff(goal[1])) as u32;
+        let (path, cost) = quantized_focal_astar(
+            &walls_quantizer(), [0.0, 0.0], 0.5, open, manhattan,
+            |&[x, y]: &[usize; 2]| (x == 2 && y == 0, manhattan(&[x, y])),
+            |n| *n == goal, |c| c as f32,
+        ).unwrap();
+        assert!(cost <= 7);
+        assert!(!path.contains(&[2.0, 0.0]));
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let blocked_goal = |n: &[usize; 2]| *n == [10, 0];
+        assert!(quantized_weighted_astar(
+            &walls_quantizer(), [0.0, 0.0], 1.0, walls_successors, walls_octile, blocked_goal, |c| c as f32,
+        ).is_none());
+        assert!(quantized_focal_astar(
+            &walls_quantizer(), [0.0, 0.0], 1.0, walls_successors, walls_octile, walls_octile, blocked_goal, |c| c as f32,
+        ).is_none());
+    }
+}